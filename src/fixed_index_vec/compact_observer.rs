@@ -0,0 +1,11 @@
+/// Observes the moves made while compressing a [super::FixedIndexVec] through
+/// [super::FixedIndexVec::compress_observed], so that compressions can be monitored without
+/// handling their results on every call site.
+pub trait CompactObserver<Idx = usize> {
+    /// Called for every value or reservation moved from the old index into the new one, in the
+    /// same order as they appear on [super::compress_result::CompressResult].
+    fn on_move(&self, old: Idx, new: Idx);
+
+    /// Called once the compression is complete, telling how many moves were made.
+    fn on_complete(&self, moves: usize);
+}
@@ -2,18 +2,63 @@ use alloc::vec::Vec;
 
 /// Holds the result of executing [super::FixedIndexVec::compress], which is a Vector containing
 /// every index that has changed along its new value.
-pub struct CompressResult(pub Vec<(usize, usize)>);
+pub struct CompressResult<Idx = usize>(pub Vec<(Idx, Idx)>);
+
+/// Holds statistics about the work done by [super::FixedIndexVec::compress_stats], useful to
+/// decide when compressing is worth it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CompressStats {
+    /// Amount of values that were relocated into an empty position.
+    pub moves: usize,
+    /// Amount of positions that were freed from the end of the Vec.
+    pub reclaimed_slots: usize,
+    /// Length of the Vec once compressed.
+    pub final_len: usize,
+}
+
+impl<Idx: Copy> CompressResult<Idx> {
+    /// Returns a [CompressResult] where every pair is reversed, holding every new index followed by
+    /// the index it had before compressing, useful to update indexes stored as new to old.
+    pub fn inverted(&self) -> CompressResult<Idx> {
+        CompressResult(self.0.iter().map(|&(old_index, new_index)| (new_index, old_index)).collect())
+    }
 
-impl CompressResult {
     /// Replaces all values on in this iterator that matches to an the old indexes with the new
     /// indexes.
     pub fn update_old_indexes<'old_indexes, IndexType>(&self, old_indexes_iter: impl Iterator<Item=&'old_indexes mut IndexType>)
-        where IndexType: From<usize> + PartialEq<usize> + 'old_indexes {
+        where IndexType: From<Idx> + PartialEq<Idx> + 'old_indexes {
         old_indexes_iter.for_each(|value| {
-            match self.0.iter().filter(|(changed_index, _)| *value == *changed_index).next() {
+            match self.0.iter().find(|(changed_index, _)| *value == *changed_index) {
                 None => {}
                 Some((_, new_index)) => { *value = (*new_index).into() }
             }
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::fixed_index_vec::FixedIndexVec;
+
+    #[test]
+    fn inverted_undoes_update_old_indexes() {
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+        fixed_index_vec.remove(1);
+        fixed_index_vec.remove(5);
+        let result = fixed_index_vec.compress(true);
+        let inverted = result.inverted();
+        assert_eq!(inverted.0, vec![(1, 7), (5, 6)]);
+
+        let original: Vec<usize> = (0..8).filter(|index| ![1, 5].contains(index)).collect();
+        let mut indexes = original.clone();
+        result.update_old_indexes(indexes.iter_mut());
+        for (index, value) in indexes.iter().zip(&original) {
+            assert_eq!(fixed_index_vec.get(*index), Some(&(*value as u32)));
+        }
+        inverted.update_old_indexes(indexes.iter_mut());
+        assert_eq!(indexes, original);
+    }
+}
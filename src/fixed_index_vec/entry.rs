@@ -0,0 +1,77 @@
+use crate::fixed_index_vec::FixedIndexVec;
+use crate::fixed_index_vec::vec_index::VecIndex;
+
+/// Refers to a single index of a [FixedIndexVec], whether it holds a value or not, returned by
+/// [FixedIndexVec::entry].
+pub struct Entry<'values, Value, Idx: VecIndex> {
+    pub(super) fixed_index_vec: &'values mut FixedIndexVec<Value, Idx>,
+    pub(super) index: Idx,
+}
+
+impl<'values, Value, Idx: VecIndex> Entry<'values, Value, Idx> {
+    /// Returns the index this entry refers to.
+    pub fn index(&self) -> Idx {
+        self.index
+    }
+
+    /// Returns whether the index this entry refers to holds a value.
+    pub fn is_occupied(&self) -> bool {
+        self.fixed_index_vec.contains_index(self.index)
+    }
+
+    /// Returns a mutable reference to the value of this entry, storing the default value on it
+    /// first if it held no value, as [FixedIndexVec::get_or_insert] does.
+    pub fn or_insert(self, default: Value) -> &'values mut Value {
+        self.fixed_index_vec.get_or_insert(self.index, default)
+    }
+
+    /// Returns a mutable reference to the value of this entry, storing the value built by the
+    /// function from this entry's index first if it held no value, as
+    /// [Entry::or_insert] does, but only building the value when needed.
+    pub fn or_insert_with_key<F: FnOnce(Idx) -> Value>(self, f: F) -> &'values mut Value {
+        if self.is_occupied() { return self.fixed_index_vec.get_mut(self.index).unwrap(); }
+        self.fixed_index_vec.get_or_insert(self.index, f(self.index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::fixed_index_vec::FixedIndexVec;
+
+    #[test]
+    fn occupied_entry_reports_its_index_and_keeps_its_value() {
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+        let entry = fixed_index_vec.entry(2);
+        assert_eq!(entry.index(), 2);
+        assert!(entry.is_occupied());
+        assert_eq!(*entry.or_insert(9), 2);
+    }
+
+    #[test]
+    fn vacant_entry_reports_its_index_until_filled() {
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+        fixed_index_vec.remove(1);
+        let entry = fixed_index_vec.entry(1);
+        assert_eq!(entry.index(), 1);
+        assert!(!entry.is_occupied());
+        assert_eq!(*entry.or_insert(5), 5);
+        assert!(fixed_index_vec.entry(1).is_occupied());
+        assert!(!fixed_index_vec.entry(7).is_occupied());
+        assert_eq!(fixed_index_vec.len(), 3);
+    }
+
+    #[test]
+    fn or_insert_with_key_builds_values_from_their_own_index() {
+        let mut fixed_index_vec: FixedIndexVec<usize> = FixedIndexVec::new();
+        assert_eq!(*fixed_index_vec.entry(3).or_insert_with_key(|index| index * 10), 30);
+        assert_eq!(*fixed_index_vec.entry(3).or_insert_with_key(|_| unreachable!()), 30);
+        let reserved = fixed_index_vec.reserve_pos();
+        assert_eq!(*fixed_index_vec.entry(reserved).or_insert_with_key(|index| index * 10), reserved * 10);
+        assert_eq!(fixed_index_vec.iter_index().collect::<Vec<_>>(), vec![(0, &0), (3, &30)]);
+        assert_eq!(fixed_index_vec.reserved_spaces_len(), 0);
+        assert_eq!(fixed_index_vec.lowest_free_index(), 1);
+    }
+}
@@ -0,0 +1,119 @@
+use core::fmt::{Debug, Display, Formatter};
+
+/// Describes why an operation over a specific index of a [super::FixedIndexVec] failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixedIndexError {
+    /// The index is beyond the end of the Vec.
+    OutOfBounds,
+    /// The index is within bounds, but its position is not [super::pos::Pos::Reserved].
+    NotReserved,
+}
+
+impl Display for FixedIndexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FixedIndexError::OutOfBounds => f.write_str("index is out of bounds"),
+            FixedIndexError::NotReserved => f.write_str("position is not reserved"),
+        }
+    }
+}
+
+/// Describes which index made [super::FixedIndexVec::get_many_mut] fail and why.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GetManyError<Idx = usize> {
+    /// The index was requested more than once, so it can't be borrowed mutably for each request.
+    Duplicate(Idx),
+    /// The index is within bounds, but its position holds no value.
+    NotPresent(Idx),
+    /// The index is beyond the end of the Vec.
+    OutOfBounds(Idx),
+}
+
+impl<Idx: Debug> Display for GetManyError<Idx> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            GetManyError::Duplicate(index) => write!(f, "index {index:?} is requested more than once"),
+            GetManyError::NotPresent(index) => write!(f, "index {index:?} holds no value"),
+            GetManyError::OutOfBounds(index) => write!(f, "index {index:?} is out of bounds"),
+        }
+    }
+}
+
+/// Describes the first move of a plan sent to [super::FixedIndexVec::compress_with_plan] that can't
+/// be applied, being `move_index` its position within the plan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompressPlanError<Idx = usize> {
+    /// The source index holds no value, or is out of bounds, at the time of moving it.
+    SourceNotUsed { move_index: usize, source: Idx },
+    /// The target index isn't an empty position, or is out of bounds, at the time of moving into it.
+    TargetNotEmpty { move_index: usize, target: Idx },
+}
+
+impl<Idx: Debug> Display for CompressPlanError<Idx> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CompressPlanError::SourceNotUsed { move_index, source } => write!(f, "move {move_index} takes from index {source:?}, which holds no value"),
+            CompressPlanError::TargetNotEmpty { move_index, target } => write!(f, "move {move_index} moves into index {target:?}, which is not empty"),
+        }
+    }
+}
+
+/// Describes the first index that was repeated when building a [super::FixedIndexVec] through
+/// [super::FixedIndexVec::try_from_pairs].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DuplicateIndexError<Idx = usize> {
+    /// The index that was found more than once.
+    pub index: Idx,
+}
+
+impl<Idx: Debug> Display for DuplicateIndexError<Idx> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "index {:?} is found more than once", self.index)
+    }
+}
+
+/// Describes why the parts sent to [super::FixedIndexVec::from_parts], or those of a
+/// [super::FixedIndexVec] checked through [super::FixedIndexVec::verify_invariants], are
+/// inconsistent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidPartsError<Idx = usize> {
+    /// The vacancy doesn't point to an empty position, or is out of bounds.
+    VacancyNotEmpty(Idx),
+    /// The vacancies aren't sorted by ascending index, or an index is repeated.
+    UnsortedVacancies,
+    /// There are empty positions that aren't registered as vacancies.
+    UnregisteredEmptyPositions { vacancies: usize, empty_positions: usize },
+    /// The amount of reserved positions doesn't match the reserved count.
+    ReservedCountMismatch { reserved_spaces: usize, reserved_positions: usize },
+    /// The last position is empty, even though [super::FixedIndexVec::clean_right] removes them.
+    TrailingEmpty,
+}
+
+impl<Idx: Debug> Display for InvalidPartsError<Idx> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            InvalidPartsError::VacancyNotEmpty(index) => write!(f, "vacancy {index:?} is not an empty position"),
+            InvalidPartsError::UnsortedVacancies => f.write_str("vacancies are not sorted ascending without repetitions"),
+            InvalidPartsError::UnregisteredEmptyPositions { vacancies, empty_positions } => write!(f, "there are {empty_positions} empty positions, but only {vacancies} vacancies"),
+            InvalidPartsError::ReservedCountMismatch { reserved_spaces, reserved_positions } => write!(f, "there are {reserved_positions} reserved positions, but the reserved count is {reserved_spaces}"),
+            InvalidPartsError::TrailingEmpty => f.write_str("the last position is empty"),
+        }
+    }
+}
+
+/// Describes which invariant of a [super::FixedIndexVec] was found broken by
+/// [super::FixedIndexVec::verify_invariants].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvariantError<Idx = usize> {
+    /// The vacancies or the reserved count don't match the positions, or the last position is
+    /// empty, as described by the [InvalidPartsError].
+    Inconsistent(InvalidPartsError<Idx>),
+}
+
+impl<Idx: Debug> Display for InvariantError<Idx> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            InvariantError::Inconsistent(error) => Display::fmt(error, f),
+        }
+    }
+}
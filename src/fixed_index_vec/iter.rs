@@ -0,0 +1,209 @@
+use alloc::vec;
+use core::slice;
+use core::iter::FusedIterator;
+
+use crate::fixed_index_vec::FixedIndexVec;
+use crate::fixed_index_vec::pos::Pos;
+use crate::fixed_index_vec::vacancy_guard::VacancyGuard;
+use crate::fixed_index_vec::vec_index::VecIndex;
+
+/// Iterator referencing every value of a [super::FixedIndexVec], returned by
+/// [super::FixedIndexVec::iter].
+pub struct Iter<'values, Value> {
+    pub(super) positions: slice::Iter<'values, Pos<Value>>,
+    pub(super) remaining: usize,
+}
+
+impl<'values, Value> Iterator for Iter<'values, Value> {
+    type Item = &'values Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.positions.find_map(Pos::as_opt_ref)?;
+        self.remaining = self.remaining.saturating_sub(1);
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<Value> ExactSizeIterator for Iter<'_, Value> {}
+
+/// Iterator over mutable references to every value of a [super::FixedIndexVec], returned by
+/// [super::FixedIndexVec::iter_mut].
+pub struct IterMut<'values, Value> {
+    pub(super) positions: slice::IterMut<'values, Pos<Value>>,
+    pub(super) remaining: usize,
+}
+
+impl<'values, Value> Iterator for IterMut<'values, Value> {
+    type Item = &'values mut Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.positions.find_map(Pos::as_opt_mut)?;
+        self.remaining = self.remaining.saturating_sub(1);
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<Value> ExactSizeIterator for IterMut<'_, Value> {}
+
+/// In-Place iterator over every value of a [super::FixedIndexVec], returned by
+/// [super::FixedIndexVec::into_iter].
+pub struct IntoIter<Value> {
+    pub(super) positions: vec::IntoIter<Pos<Value>>,
+    pub(super) remaining: usize,
+}
+
+impl<Value> Iterator for IntoIter<Value> {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.positions.find_map(Pos::opt)?;
+        self.remaining = self.remaining.saturating_sub(1);
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<Value> ExactSizeIterator for IntoIter<Value> {}
+
+/// Once the positions run out, they never yield anything again.
+impl<Value> FusedIterator for IntoIter<Value> {}
+
+/// Iterator removing and yielding every value along its index for which a predicate returns true,
+/// returned by [super::FixedIndexVec::drain_filter].
+/// <br>
+/// <br>
+/// Once dropped, even if the predicate panics, empty positions are registered and
+/// [super::FixedIndexVec::clean_right] is performed, values that weren't reached by the iterator
+/// are left in place.
+pub struct DrainFilter<'values, Value, Idx: VecIndex, F: FnMut(Idx, &mut Value) -> bool> {
+    pub(super) guard: VacancyGuard<'values, Value, Idx>,
+    pub(super) position: usize,
+    pub(super) pred: F,
+}
+
+impl<Value, Idx: VecIndex, F: FnMut(Idx, &mut Value) -> bool> Iterator for DrainFilter<'_, Value, Idx, F> {
+    type Item = (Idx, Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.guard.fixed_index_vec.values.len() {
+            let position = self.position;
+            self.position += 1;
+            let index = FixedIndexVec::<Value, Idx>::index_of(position);
+            let matches = match &mut self.guard.fixed_index_vec.values[position] {
+                Pos::Used(value) => (self.pred)(index, value),
+                _ => false,
+            };
+            if !matches { continue; }
+            return Some((index, self.guard.take(position)));
+        }
+        None
+    }
+}
+
+impl<Value, Idx: VecIndex, F: FnMut(Idx, &mut Value) -> bool> Drop for DrainFilter<'_, Value, Idx, F> {
+    fn drop(&mut self) {
+        self.guard.register();
+        self.guard.fixed_index_vec.debug_verify_invariants();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::iter::FusedIterator;
+
+    use crate::fixed_index_vec::FixedIndexVec;
+
+    #[test]
+    fn iter_reports_the_used_count_as_its_exact_length() {
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+        fixed_index_vec.remove(2);
+        fixed_index_vec.reserve_pos();
+        fixed_index_vec.remove(4);
+        let mut iter = fixed_index_vec.iter();
+        assert_eq!(iter.len(), fixed_index_vec.used_spaces_len());
+        assert_eq!(iter.len(), 6);
+        iter.next();
+        iter.next();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.count(), 4);
+    }
+
+    #[test]
+    fn iter_mut_and_into_iter_report_their_exact_length() {
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+        fixed_index_vec.remove(2);
+        fixed_index_vec.remove(4);
+        let mut iter_mut = fixed_index_vec.iter_mut();
+        assert_eq!(iter_mut.len(), 6);
+        iter_mut.next();
+        assert_eq!(iter_mut.len(), 5);
+        let mut into_iter = fixed_index_vec.into_iter();
+        assert_eq!(into_iter.len(), 6);
+        into_iter.next();
+        assert_eq!(into_iter.len(), 5);
+        assert_eq!(into_iter.collect::<Vec<_>>(), vec![1, 3, 5, 6, 7]);
+    }
+
+    #[test]
+    fn drain_filter_removes_every_matching_value_when_consumed() {
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+        fixed_index_vec.remove(3);
+        let drained: Vec<_> = fixed_index_vec.drain_filter(|_, value| *value % 2 == 1).collect();
+        assert_eq!(drained, vec![(1, 1), (5, 5), (7, 7)]);
+        //The trailing position 7 is trimmed, while 1, 3 and 5 are left to be reused
+        assert_eq!(fixed_index_vec.len(), 7);
+        assert_eq!(fixed_index_vec.empty_spaces_len(), 3);
+        assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+        for index in [1, 3, 5] {
+            assert_eq!(fixed_index_vec.push(10), index);
+        }
+    }
+
+    #[test]
+    fn drain_filter_keeps_unvisited_values_when_dropped_early() {
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+        {
+            let mut drain = fixed_index_vec.drain_filter(|_, value| *value >= 2);
+            assert_eq!(drain.next(), Some((2, 2)));
+        }
+        assert_eq!(fixed_index_vec.used_spaces_len(), 7);
+        assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+        assert_eq!(fixed_index_vec.push(1), 2);
+    }
+
+    #[test]
+    fn drain_filter_can_modify_the_values_it_keeps() {
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+        let drained: Vec<_> = fixed_index_vec.drain_filter(|index, value| { *value *= 10; index == 0 }).collect();
+        assert_eq!(drained, vec![(0, 0)]);
+        assert_eq!(fixed_index_vec.iter().copied().collect::<Vec<_>>(), vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn into_iter_is_fused_and_collects_without_reallocating() {
+        fn assert_fused<Iter: FusedIterator + ExactSizeIterator>(iter: Iter) -> Iter { iter }
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..10).collect();
+        fixed_index_vec.remove(3);
+        let mut iter = assert_fused(fixed_index_vec.into_iter());
+        assert_eq!(iter.len(), 9);
+        let mut values = Vec::with_capacity(iter.len());
+        values.extend(&mut iter);
+        assert_eq!(values.capacity(), 9);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.len(), 0);
+    }
+}
@@ -1,68 +1,434 @@
 use alloc::collections::VecDeque;
 use alloc::vec::Vec;
 use core::mem;
+use core::ops::Range;
 
-use compress_result::CompressResult;
+use compact_observer::CompactObserver;
+use compress_result::{CompressResult, CompressStats};
+use entry::Entry;
+use error::{CompressPlanError, DuplicateIndexError, FixedIndexError, GetManyError, InvalidPartsError, InvariantError};
+use iter::{DrainFilter, IntoIter, Iter, IterMut};
+use reservation_guard::ReservationGuard;
+use reuse_policy::ReusePolicy;
+use swap_remove_outcome::SwapRemoveOutcome;
+use update_action::UpdateAction;
+use vacancy_guard::{TakenValueGuard, VacancyGuard};
+use vec_index::VecIndex;
 
 use self::pos::Pos;
 use self::pos::Pos::*;
 
+/// Defines the observers of the moves made by [FixedIndexVec::compress_observed]
+pub mod compact_observer;
+
 /// Defines the result of a [FixedIndexVec::compress]
 pub mod compress_result;
 
+/// Defines the entries referring to a single index of a [FixedIndexVec]
+pub mod entry;
+
+/// Defines errors returned by fallible operations of [FixedIndexVec]
+pub mod error;
+
+/// Defines the iterators over the values of a [FixedIndexVec]
+pub mod iter;
+
 /// Defines positions that are stored in [FixedIndexVec]
 pub mod pos;
 
+/// Defines the guards cancelling reservations of a [FixedIndexVec] that are never filled
+pub mod reservation_guard;
+
+/// Defines the policies deciding which empty position of a [FixedIndexVec] gets reused first
+pub mod reuse_policy;
+
+/// Defines the outcome of a [FixedIndexVec::swap_remove]
+pub mod swap_remove_outcome;
+
+/// Defines what [FixedIndexVec::update] does with a value after updating it
+pub mod update_action;
+
+/// Defines the types that can be used as indexes of a [FixedIndexVec]
+pub mod vec_index;
+
 /// Contains specific trait implementations of [FixedIndexVec] that are commonly used by Rust's
 /// collections
 mod trait_impls;
 
+/// Defines the guards registering emptied positions as vacancies, even while unwinding
+mod vacancy_guard;
+
+/// Contains the tests of the methods of [FixedIndexVec]
+#[cfg(test)]
+mod tests;
+
 
 /// Vec-like structure where indexes are kept for values even when removing others, usually used to
 /// replace HashMap<usize, T> on environments where std can't reach or when performance of accessing
 /// by index is extremely important but can do get it at the expense of memory allocation,
-/// especially when the removal operation is not required or used often as every operation is O(1),
-/// where the access is done through a Vec, not requiring hashing operations.
-#[derive(Clone, Debug, Eq)]
-pub struct FixedIndexVec<Value> {
+/// especially when the removal operation is not required or used often as pushing and accessing are
+/// O(1) under the default [ReusePolicy], where the access is done through a Vec, not requiring
+/// hashing operations.
+/// <br>
+/// <br>
+/// Indexes are usize by default, but any [VecIndex] can be used instead, such as u32, to reduce the
+/// memory used by empty positions, limiting how many positions there can be.
+/// <br>
+/// <br>
+/// Values don't need to implement [Clone] nor [Default], as pushing, removing and compressing only
+/// move them by swapping positions or taking them out, only methods that clone values, such as
+/// [FixedIndexVec::get_cloned], require [Clone].
+#[derive(Debug, Eq)]
+pub struct FixedIndexVec<Value, Idx = usize> {
     /// Holds positions where the values are stored, although these positions can also be empty or
     /// reserved.
     values: Vec<Pos<Value>>,
     /// Holds indexes pointing where empty positions are found.
-    vacancies: VecDeque<usize>,
+    vacancies: VecDeque<Idx>,
     /// Current amount of empty spaces.
     reserved_spaces: usize,
+    /// Decides which empty position is reused first.
+    reuse_policy: ReusePolicy,
+    /// Holds the indexes of freed positions in the order they were freed, only while
+    /// [ReusePolicy::MostRecent] is set, some of them might have been reused or trimmed since.
+    recently_freed: Vec<Idx>,
+    /// Whether [FixedIndexVec::clean_right] releases unused memory after trimming.
+    auto_shrink: bool,
+    /// Holds how many times each position was emptied, this is only kept on debug builds to catch
+    /// indexes that were used after their value was removed, see [FixedIndexVec::get_checked].
+    #[cfg(debug_assertions)]
+    generations: Vec<u32>,
 }
 
 impl<Value> FixedIndexVec<Value> {
     /// Creates an empty FixedIndexVec.
     pub const fn new() -> FixedIndexVec<Value> {
+        Self::with_index_type()
+    }
+
+    /// Creates a FixedIndexVec holding just this value, returning it along the index of said value,
+    /// which is always 0.
+    pub fn singleton(value: Value) -> (FixedIndexVec<Value>, usize) {
+        let mut fixed_index_vec = Self::new();
+        let index = fixed_index_vec.push(value);
+        (fixed_index_vec, index)
+    }
+
+    /// Creates a FixedIndexVec where each [Option::Some] is stored as a value on the same index it
+    /// was found on, while each [Option::None] is left as an empty position, trimming those found
+    /// on the right end.
+    pub fn from_options(iter: impl IntoIterator<Item=Option<Value>>) -> FixedIndexVec<Value> {
+        let mut fixed_index_vec = Self::new();
+        fixed_index_vec.extend_positions(iter.into_iter().map(|value| value.map_or(Empty, Used)));
+        fixed_index_vec
+    }
+
+    /// Creates an empty FixedIndexVec with room for `values_cap` positions and `vacancy_cap` empty
+    /// positions, so that neither of them reallocates while removing or pushing within those
+    /// capacities.
+    pub fn with_capacity_and_vacancies(values_cap: usize, vacancy_cap: usize) -> FixedIndexVec<Value> {
+        let mut fixed_index_vec = Self::new();
+        fixed_index_vec.values.reserve_exact(values_cap);
+        fixed_index_vec.vacancies.reserve_exact(vacancy_cap);
+        fixed_index_vec
+    }
+}
+
+impl<Value, Idx: VecIndex> FixedIndexVec<Value, Idx> {
+    /// Creates an empty FixedIndexVec whose indexes are of the type Idx instead of usize.
+    pub const fn with_index_type() -> FixedIndexVec<Value, Idx> {
         Self {
             values: Vec::new(),
             vacancies: VecDeque::new(),
             reserved_spaces: 0,
+            reuse_policy: ReusePolicy::LowestIndex,
+            recently_freed: Vec::new(),
+            auto_shrink: false,
+            #[cfg(debug_assertions)]
+            generations: Vec::new(),
+        }
+    }
+
+    /// Creates a FixedIndexVec holding every value on its index, where gaps between indexes are
+    /// left as empty positions, building it in a single pass.
+    /// <br>
+    /// <br>
+    /// The pairs must be sorted by ascending index with no index repeated, otherwise values end up
+    /// on unspecified indexes.
+    /// <br>
+    /// <br>
+    /// # Panics
+    /// On debug builds, panics if the pairs aren't sorted by ascending index or an index repeats.
+    pub fn from_sorted_pairs(pairs: impl IntoIterator<Item=(Idx, Value)>) -> FixedIndexVec<Value, Idx> {
+        let mut fixed_index_vec = Self::with_index_type();
+        for (index, value) in pairs {
+            let position = index.into_usize();
+            debug_assert!(position >= fixed_index_vec.values.len(), "FixedIndexVec::from_sorted_pairs received index {position} after index {}", fixed_index_vec.values.len().wrapping_sub(1));
+            while fixed_index_vec.values.len() < position {
+                fixed_index_vec.vacancies.push_back(Self::index_of(fixed_index_vec.values.len()));
+                fixed_index_vec.values.push(Empty);
+            }
+            fixed_index_vec.values.push(Used(value));
         }
+        fixed_index_vec
+    }
+
+    /// Creates a FixedIndexVec holding every value on its index, in any order, where indexes no pair
+    /// refers to are left as empty positions, returning a [DuplicateIndexError] with the first index
+    /// that is repeated instead of overwriting its value.
+    pub fn try_from_pairs(pairs: impl IntoIterator<Item=(Idx, Value)>) -> Result<FixedIndexVec<Value, Idx>, DuplicateIndexError<Idx>> {
+        let mut fixed_index_vec = Self::with_index_type();
+        for (index, value) in pairs {
+            if !fixed_index_vec.claim_empty_pos(index) { return Err(DuplicateIndexError { index }); }
+            fixed_index_vec.values[index.into_usize()] = Used(value);
+        }
+        Ok(fixed_index_vec)
+    }
+
+    /// Creates a FixedIndexVec from the parts returned by [FixedIndexVec::into_parts], being the
+    /// positions, the indexes of the empty positions sorted ascending and the amount of reserved
+    /// positions, returning an [InvalidPartsError] if they aren't consistent with each other or
+    /// the last position is empty.
+    pub fn from_parts(values: Vec<Pos<Value>>, vacancies: VecDeque<Idx>, reserved_spaces: usize) -> Result<FixedIndexVec<Value, Idx>, InvalidPartsError<Idx>> {
+        Self::check_parts(&values, &vacancies, reserved_spaces)?;
+        let mut fixed_index_vec = Self::with_index_type();
+        fixed_index_vec.values = values;
+        fixed_index_vec.vacancies = vacancies;
+        fixed_index_vec.reserved_spaces = reserved_spaces;
+        Ok(fixed_index_vec)
+    }
+
+    /// Checks the vacancies point to every empty position sorted ascending, the reserved count
+    /// matches the amount of reserved positions and the last position isn't empty.
+    fn check_parts(values: &[Pos<Value>], vacancies: &VecDeque<Idx>, reserved_spaces: usize) -> Result<(), InvalidPartsError<Idx>> {
+        if let Some(&vacancy) = vacancies.iter().find(|vacancy| !values.get(vacancy.into_usize()).is_some_and(Pos::is_empty)) {
+            return Err(InvalidPartsError::VacancyNotEmpty(vacancy));
+        }
+        if vacancies.iter().zip(vacancies.iter().skip(1)).any(|(previous, next)| previous >= next) {
+            return Err(InvalidPartsError::UnsortedVacancies);
+        }
+        let empty_positions = values.iter().filter(|pos| pos.is_empty()).count();
+        if empty_positions != vacancies.len() {
+            return Err(InvalidPartsError::UnregisteredEmptyPositions { vacancies: vacancies.len(), empty_positions });
+        }
+        let reserved_positions = values.iter().filter(|pos| pos.is_reserved()).count();
+        if reserved_positions != reserved_spaces {
+            return Err(InvalidPartsError::ReservedCountMismatch { reserved_spaces, reserved_positions });
+        }
+        if values.last().is_some_and(Pos::is_empty) { return Err(InvalidPartsError::TrailingEmpty); }
+        Ok(())
+    }
+
+    /// Checks every invariant this FixedIndexVec relies on, being that vacancies point to every
+    /// empty position sorted ascending, the reserved count matches the amount of reserved positions
+    /// and the last position isn't empty, returning an [InvariantError] with the first one that
+    /// is broken, which is the same check [FixedIndexVec::from_parts] performs.
+    /// <br>
+    /// <br>
+    /// This is O(n) and meant for tests and debugging, on debug builds, it's already checked after
+    /// every operation touching many positions at once, such as [FixedIndexVec::compress].
+    pub fn verify_invariants(&self) -> Result<(), InvariantError<Idx>> {
+        Self::check_parts(&self.values, &self.vacancies, self.reserved_spaces).map_err(InvariantError::Inconsistent)
+    }
+
+    /// Panics if any invariant is broken, this is only checked on debug builds.
+    fn debug_verify_invariants(&self) {
+        #[cfg(debug_assertions)]
+        if let Err(error) = self.verify_invariants() {
+            panic!("FixedIndexVec invariant broken: {error}");
+        }
+    }
+
+    /// Consumes this FixedIndexVec returning its parts, being its positions, the indexes of its
+    /// empty positions sorted ascending and its amount of reserved positions, which can be turned
+    /// back into a FixedIndexVec through [FixedIndexVec::from_parts].
+    /// <br>
+    /// <br>
+    /// Note its [ReusePolicy] and whether [FixedIndexVec::set_auto_shrink] was enabled are not kept.
+    pub fn into_parts(self) -> (Vec<Pos<Value>>, VecDeque<Idx>, usize) {
+        (self.values, self.vacancies, self.reserved_spaces)
+    }
+
+    /// Converts the position of a value into its index, this is always possible for existing
+    /// positions, as they can't be created if their index can't be represented.
+    fn index_of(position: usize) -> Idx {
+        Idx::from_usize(position).unwrap()
+    }
+
+    /// Returns the index for a position to be created at the end of the Vec.
+    /// <br>
+    /// <br>
+    /// # Panics
+    /// Panics if the index type can't represent it.
+    fn next_end_index(&self) -> Idx {
+        Idx::from_usize(self.values.len()).expect("FixedIndexVec has no index left that its index type can represent")
+    }
+
+    /// Sets which empty position is reused first by [FixedIndexVec::push],
+    /// [FixedIndexVec::reserve_pos] and [Extend::extend], being [ReusePolicy::LowestIndex] by
+    /// default.
+    pub fn set_reuse_policy(&mut self, policy: ReusePolicy) {
+        self.reuse_policy = policy;
+        self.recently_freed.clear();
+    }
+
+    /// Sets whether [FixedIndexVec::clean_right] should release memory once it trims the Vec,
+    /// being disabled by default.
+    /// <br>
+    /// <br>
+    /// When enabled, the Vec is shrunk to fit its length whenever its capacity exceeds four times
+    /// its length after trimming, as shrinking reallocates in O(n), this factor makes sure
+    /// shrinking happens rarely enough for its cost to be amortized among the removals that
+    /// caused it, while pushing afterward doesn't reallocate right away.
+    pub fn set_auto_shrink(&mut self, enabled: bool) {
+        self.auto_shrink = enabled;
+    }
+
+    /// Returns which empty position is reused first when pushing or reserving.
+    pub fn reuse_policy(&self) -> ReusePolicy {
+        self.reuse_policy
+    }
+
+    /// Takes out the vacancy that must be reused next according to the [ReusePolicy].
+    fn pop_vacancy(&mut self) -> Option<Idx> {
+        if self.reuse_policy == ReusePolicy::MostRecent {
+            //Freed indexes that were reused or trimmed since are no longer vacancies, so they are skipped
+            while let Some(index) = self.recently_freed.pop() {
+                if let Ok(vacancy_pos) = self.vacancies.binary_search(&index) {
+                    return self.vacancies.remove(vacancy_pos);
+                }
+            }
+        }
+        self.vacancies.pop_front()
+    }
+
+    /// Records the order in which these indexes were freed while [ReusePolicy::MostRecent] is set,
+    /// they must already be registered as vacancies.
+    fn note_freed(&mut self, freed_indexes: &[Idx]) {
+        if self.reuse_policy != ReusePolicy::MostRecent { return; }
+        self.recently_freed.extend_from_slice(freed_indexes);
+        //Stale indexes are only discarded once they might outnumber the vacancies, amortizing the cost
+        if self.recently_freed.len() > 2 * self.vacancies.len() + 16 {
+            self.discard_stale_recently_freed();
+        }
+    }
+
+    /// Keeps only the last time each index that is still a vacancy was freed, in the same order.
+    fn discard_stale_recently_freed(&mut self) {
+        let mut latest = self.recently_freed.iter()
+            .enumerate()
+            .map(|(order, &index)| (index, order))
+            .collect::<Vec<_>>();
+        latest.sort_unstable_by(|(index_a, order_a), (index_b, order_b)| index_a.cmp(index_b).then(order_b.cmp(order_a)));
+        latest.dedup_by_key(|(index, _)| *index);
+        latest.retain(|(index, _)| self.vacancies.binary_search(index).is_ok());
+        latest.sort_unstable_by_key(|&(_, order)| order);
+        self.recently_freed = latest.into_iter().map(|(index, _)| index).collect();
     }
 
     /// Pushes the value into the Vec and returns the index where said value was stored, allocating
     /// only if there was no empty space left out by a previous remove operation.
     /// <br>
     /// <br>
-    /// This operation is O(1).
-    pub fn push(&mut self, value: Value) -> usize {
-        match self.vacancies.pop_front() {
+    /// Empty positions are reused according to the [ReusePolicy], which by default starts from the
+    /// lowest index, meaning the value is stored at [FixedIndexVec::lowest_free_index].
+    /// <br>
+    /// <br>
+    /// This operation is O(1) under the default [ReusePolicy::LowestIndex], while under
+    /// [ReusePolicy::MostRecent] it's O(n) in the worst case, as the reused position is taken out
+    /// of the middle of the sorted empty positions, skipping freed indexes that are no longer empty.
+    /// <br>
+    /// <br>
+    /// # Panics
+    /// Panics if there is no empty position and the index type can't represent a new one.
+    pub fn push(&mut self, value: Value) -> Idx {
+        match self.pop_vacancy() {
             Some(vacant_index) => {
-                self.values[vacant_index] = Used(value);
+                self.values[vacant_index.into_usize()] = Used(value);
                 vacant_index
             }
             None => {
-                let index = self.values.len();
+                let index = self.next_end_index();
                 self.values.push(Used(value));
                 index
             }
         }
     }
 
+    /// Pushes the value as [FixedIndexVec::push] does, but returning the value back instead of
+    /// panicking if there is no empty position and the index type can't represent a new one.
+    pub fn try_push(&mut self, value: Value) -> Result<Idx, Value> {
+        if !self.has_free_index() { return Err(value); }
+        Ok(self.push(value))
+    }
+
+    /// Pushes the value as [FixedIndexVec::push] does, but only if it can be stored on an empty
+    /// position or within the current capacity, returning the value back if storing it would
+    /// require reallocating.
+    pub fn push_within_capacity(&mut self, value: Value) -> Result<Idx, Value> {
+        if self.vacancies.is_empty() && self.values.len() == self.values.capacity() { return Err(value); }
+        self.try_push(value)
+    }
+
+    /// Returns whether there is an empty position or a new one can be represented by the index
+    /// type, meaning pushing or reserving won't panic.
+    fn has_free_index(&self) -> bool {
+        !self.vacancies.is_empty() || Idx::from_usize(self.values.len()).is_some()
+    }
+
+    /// Pushes every value from the slice as [FixedIndexVec::push] would, reusing empty positions
+    /// first and allocating room for the remaining values at once.
+    pub fn extend_from_slice(&mut self, slice: &[Value]) where Value: Copy {
+        self.values.reserve(slice.len().saturating_sub(self.vacancies.len()));
+        for &value in slice {
+            self.push(value);
+        }
+    }
+
+    /// Pushes every value as [Extend::extend] does, reusing empty positions first, but returning the
+    /// indexes where they were stored in the same order.
+    pub fn extend_indexed(&mut self, iter: impl IntoIterator<Item=Value>) -> Vec<Idx> {
+        iter.into_iter().map(|value| self.push(value)).collect()
+    }
+
+    /// Appends every position at the end of the Vec as it is, whether it's used, reserved or empty,
+    /// registering empty and reserved positions, unlike [Extend::extend], which only pushes values
+    /// and reuses empty positions first.
+    /// <br>
+    /// <br>
+    /// Once appended, [FixedIndexVec::clean_right] is performed, even if the iterator panics, in
+    /// which case the positions it yielded until then stay appended.
+    /// <br>
+    /// <br>
+    /// # Panics
+    /// Panics if the index type can't represent the index of an appended position.
+    pub fn extend_positions(&mut self, iter: impl IntoIterator<Item=Pos<Value>>) {
+        //No position is freed here, the guard is only kept to perform clean_right when dropped
+        let guard = VacancyGuard::new(self);
+        for pos in iter {
+            let index = guard.fixed_index_vec.next_end_index();
+            match pos {
+                Empty => guard.fixed_index_vec.vacancies.push_back(index),
+                Reserved => guard.fixed_index_vec.reserved_spaces += 1,
+                Used(_) => {}
+            }
+            guard.fixed_index_vec.values.push(pos);
+        }
+        drop(guard);
+        self.debug_verify_invariants();
+    }
+
+    /// Returns the lowest empty position, or the end of the Vec if there are none, being the index
+    /// the next [FixedIndexVec::push] or [FixedIndexVec::reserve_pos] will use under the default
+    /// [ReusePolicy::LowestIndex].
+    /// <br>
+    /// <br>
+    /// # Panics
+    /// Panics if there is no empty position and the index type can't represent a new one.
+    pub fn lowest_free_index(&self) -> Idx {
+        self.vacancies.front().copied().unwrap_or_else(|| self.next_end_index())
+    }
+
     /// Removes a value from the vec, leaving it's space as empty and ready for other values, being
     /// an O(log n) operation, where n is the number of current empty spaces.
     /// <br>
@@ -70,13 +436,130 @@ impl<Value> FixedIndexVec<Value> {
     /// If after removing the value the vec has empty positions on it's right(end) bound, it
     /// performs [FixedIndexVec::clean_right], adding it into another O(n) operation, where n is the
     /// amount of leading empty positions on the right end.
-    pub fn remove(&mut self, index: usize) -> Option<Value> {
-        if index >= self.values.len() || self.values[index].is_empty() { return None; }
+    /// <br>
+    /// <br>
+    /// If the position is reserved, its reservation is cancelled and [Option::None] is returned.
+    pub fn remove(&mut self, index: Idx) -> Option<Value> {
+        let position = index.into_usize();
+        if position >= self.values.len() || self.values[position].is_empty() { return None; }
+        if self.values[position].is_reserved() { self.reserved_spaces -= 1; }
+        let res = mem::take(&mut self.values[position]).opt();
+        self.register_vacancy(index);
+        res
+    }
+
+    /// Registers the position of this index, which was just emptied, as a vacancy, advancing its
+    /// generation and performing [FixedIndexVec::clean_right], as [FixedIndexVec::remove] does.
+    fn register_vacancy(&mut self, index: Idx) {
         let pos = self.vacancies.partition_point(|&previous_vacancy_index| previous_vacancy_index < index);
         self.vacancies.insert(pos, index);
-        let res = mem::take(&mut self.values[index]).opt();
+        self.note_freed(&[index]);
+        self.bump_generation(index.into_usize());
         self.clean_right();
-        res
+    }
+
+    /// Replaces the value matching this index with the one the function returns from it, keeping
+    /// its index, returns true if the index held a value, or false otherwise, in which case the
+    /// function isn't called.
+    /// <br>
+    /// <br>
+    /// If the function panics, the value is lost and its index is left empty, as if it was removed
+    /// through [FixedIndexVec::remove].
+    pub fn replace_with<F: FnOnce(Value) -> Value>(&mut self, index: Idx, f: F) -> bool {
+        if !self.contains_index(index) { return false; }
+        let value = mem::take(&mut self.values[index.into_usize()]).opt().unwrap();
+        let guard = TakenValueGuard { fixed_index_vec: self, index };
+        guard.put_back(f(value));
+        true
+    }
+
+    /// Calls the function over the value matching this index, then, if it returns
+    /// [UpdateAction::Remove], removes the value as [FixedIndexVec::remove] does and returns it,
+    /// returning [Option::None] if it was kept or the index held no value.
+    pub fn update<F: FnOnce(&mut Value) -> UpdateAction>(&mut self, index: Idx, f: F) -> Option<Value> {
+        match f(self.get_mut(index)?) {
+            UpdateAction::Keep => None,
+            UpdateAction::Remove => self.remove(index),
+        }
+    }
+
+    /// Removes the value matching this index and moves the value with the highest index into its
+    /// place, so that values stay as compact as they were, returning the removed value and which
+    /// index was moved, if any.
+    pub fn swap_remove(&mut self, index: Idx) -> SwapRemoveOutcome<Value, Idx> {
+        if !self.contains_index(index) { return SwapRemoveOutcome::NotPresent; }
+        let (last_index, _) = self.last().unwrap();
+        if last_index == index { return SwapRemoveOutcome::RemovedNoMove(self.remove(index).unwrap()); }
+        let moved_value = self.remove(last_index).unwrap();
+        let position = index.into_usize();
+        let value = mem::replace(&mut self.values[position], Used(moved_value)).opt().unwrap();
+        self.bump_generation(position);
+        SwapRemoveOutcome::RemovedMoved { value, moved_from: last_index, moved_to: index }
+    }
+
+    /// Removes the value matching this index as [FixedIndexVec::remove] does, but only if the
+    /// predicate returns true for it, otherwise it's left in place and [Option::None] is returned.
+    pub fn remove_if<F: FnOnce(&Value) -> bool>(&mut self, index: Idx, pred: F) -> Option<Value> {
+        if !pred(self.get(index)?) { return None; }
+        self.remove(index)
+    }
+
+    /// Places the value back on this index, as long as its position is empty or beyond the end of
+    /// the Vec, returning the value back if the position was taken by another value or reserved.
+    /// <br>
+    /// <br>
+    /// This is useful to undo a [FixedIndexVec::remove] when nothing claimed its index meanwhile.
+    pub fn reinsert(&mut self, index: Idx, value: Value) -> Result<(), Value> {
+        if !self.claim_empty_pos(index) { return Err(value); }
+        self.values[index.into_usize()] = Used(value);
+        Ok(())
+    }
+
+    /// Allocates room so that storing a value on this index through [FixedIndexVec::reinsert],
+    /// [FixedIndexVec::get_or_insert] or [FixedIndexVec::entry] won't reallocate, including the
+    /// empty positions left between the current end of the Vec and the index.
+    pub fn reserve_for_index(&mut self, index: Idx) {
+        let position = index.into_usize();
+        if position < self.values.len() { return; }
+        self.vacancies.reserve(position - self.values.len());
+        self.values.reserve(position + 1 - self.values.len());
+    }
+
+    /// Returns a mutable reference to the value matching this index, storing the default value on
+    /// it first if it held no value, whether its position was empty, reserved or beyond the end of
+    /// the Vec.
+    pub fn get_or_insert(&mut self, index: Idx, default: Value) -> &mut Value {
+        let position = index.into_usize();
+        if position < self.values.len() && self.values[position].is_reserved() {
+            self.reserved_spaces -= 1;
+            self.values[position] = Used(default);
+        } else if self.claim_empty_pos(index) {
+            self.values[position] = Used(default);
+        }
+        self.values[position].as_opt_mut().unwrap()
+    }
+
+    /// Returns an [Entry] referring to this index, whether it holds a value or not.
+    pub fn entry(&mut self, index: Idx) -> Entry<'_, Value, Idx> {
+        Entry { fixed_index_vec: self, index }
+    }
+
+    /// Makes sure this index is an empty position no longer registered as a vacancy, so it can be
+    /// overwritten, growing the Vec with empty positions when the index is beyond its end, returns
+    /// false if the position is either used or reserved.
+    fn claim_empty_pos(&mut self, index: Idx) -> bool {
+        let position = index.into_usize();
+        if position >= self.values.len() {
+            let previous_len = self.values.len();
+            self.values.resize_with(position + 1, || Empty);
+            self.vacancies.extend((previous_len..position).map(Self::index_of));
+            return true;
+        }
+        if !self.values[position].is_empty() { return false; }
+        if let Ok(vacancy_pos) = self.vacancies.binary_search(&index) {
+            self.vacancies.remove(vacancy_pos);
+        }
+        true
     }
 
     /// Reserves an index where a value is intended to be stored was stored, allocating only if
@@ -84,24 +567,49 @@ impl<Value> FixedIndexVec<Value> {
     /// <br>
     /// <br>
     /// Note: Calling [FixedIndexVec::reserve_pos] and [FixedIndexVec::remove_reserved_pos] multiple
-    /// times in a row will cause reallocating at most just once, this is because it doesn't clear
-    /// up unnecessary empty positions as [FixedIndexVec::remove] would.
+    /// times in a row will cause reallocating at most just once, this is because trimming empty
+    /// positions on the right end keeps their memory allocated, unless
+    /// [FixedIndexVec::set_auto_shrink] is enabled.
     /// <br>
     /// <br>
-    /// This operation is O(1).
-    pub fn reserve_pos(&mut self) -> usize {
-        self.reserved_spaces += 1;
-        match self.vacancies.pop_front() {
+    /// As with [FixedIndexVec::push], empty positions are reused according to the [ReusePolicy].
+    /// <br>
+    /// <br>
+    /// This operation is O(1) under the default [ReusePolicy::LowestIndex], while under
+    /// [ReusePolicy::MostRecent] it's O(n) in the worst case, as the reused position is taken out
+    /// of the middle of the sorted empty positions, skipping freed indexes that are no longer empty.
+    /// <br>
+    /// <br>
+    /// # Panics
+    /// Panics if there is no empty position and the index type can't represent a new one.
+    pub fn reserve_pos(&mut self) -> Idx {
+        let index = match self.pop_vacancy() {
             Some(vacant_index) => {
-                self.values[vacant_index] = Reserved;
+                self.values[vacant_index.into_usize()] = Reserved;
                 vacant_index
             }
             None => {
-                let index = self.values.len();
+                let index = self.next_end_index();
                 self.values.push(Reserved);
                 index
             }
-        }
+        };
+        self.reserved_spaces += 1;
+        index
+    }
+
+    /// Reserves an index as [FixedIndexVec::reserve_pos] does, but returning [Option::None] instead
+    /// of panicking if there is no empty position and the index type can't represent a new one.
+    pub fn try_reserve_pos(&mut self) -> Option<Idx> {
+        if !self.has_free_index() { return None; }
+        Some(self.reserve_pos())
+    }
+
+    /// Reserves n positions as [FixedIndexVec::reserve_pos] would, returning their indexes, but
+    /// allocating room for all of them at once, meaning it will reallocate at most once.
+    pub fn extend_reserve(&mut self, n: usize) -> Vec<Idx> {
+        self.values.reserve(n.saturating_sub(self.vacancies.len()));
+        (0..n).map(|_| self.reserve_pos()).collect()
     }
 
     /// Pushes the value over a reserved position that was got through [FixedIndexVec::reserve_pos],
@@ -109,40 +617,122 @@ impl<Value> FixedIndexVec<Value> {
     /// <br>
     /// <br>
     /// This operation is O(1).
-    pub fn push_reserved(&mut self, reserved_pos: usize, value: Value) -> Option<Value> {
-        if reserved_pos >= self.values.len() || !self.values[reserved_pos].is_reserved() { return Some(value); }
-        self.values[reserved_pos] = Used(value);
+    pub fn push_reserved(&mut self, reserved_pos: Idx, value: Value) -> Option<Value> {
+        let position = reserved_pos.into_usize();
+        if position >= self.values.len() || !self.values[position].is_reserved() { return Some(value); }
+        self.values[position] = Used(value);
         self.reserved_spaces -= 1;
         None
     }
 
-    /// Reserves an index where a value is intended to be stored was stored, allocating only if
-    /// there was no empty space left out by a  previous remove operation.
+    /// Pushes the value over a reserved position as [FixedIndexVec::push_reserved] does, but
+    /// returning the index on success, or the value back along a [FixedIndexError] telling whether
+    /// the index was out of bounds or its position wasn't reserved.
     /// <br>
     /// <br>
-    /// Note if you call [FixedIndexVec::reserve_pos] and [FixedIndexVec::remove_reserved_pos]
-    /// multiple times in a row, it will reallocate at most just once.
+    /// This operation is O(1).
+    pub fn try_push_reserved(&mut self, reserved_pos: Idx, value: Value) -> Result<Idx, (Value, FixedIndexError)> {
+        let position = reserved_pos.into_usize();
+        if position >= self.values.len() { return Err((value, FixedIndexError::OutOfBounds)); }
+        if !self.values[position].is_reserved() { return Err((value, FixedIndexError::NotReserved)); }
+        self.values[position] = Used(value);
+        self.reserved_spaces -= 1;
+        Ok(reserved_pos)
+    }
+
+    /// Cancels a reservation that was got through [FixedIndexVec::reserve_pos], returning false if
+    /// the index sent isn't an actual reserved position.
     /// <br>
     /// <br>
-    /// This operation is O(1).
-    pub fn remove_reserved_pos(&mut self, reserved_pos: usize) -> bool {
-        if reserved_pos >= self.values.len() || !self.values[reserved_pos].is_reserved() { return false; }
-        self.values[reserved_pos] = Empty;
+    /// The index is left as an empty position to be reused, just as [FixedIndexVec::remove] does,
+    /// performing [FixedIndexVec::clean_right] if it was on the right end.
+    /// <br>
+    /// <br>
+    /// Note: Calling [FixedIndexVec::reserve_pos] and [FixedIndexVec::remove_reserved_pos] multiple
+    /// times in a row will cause reallocating at most just once, as trimming a cancelled
+    /// reservation on the right end keeps its memory allocated, unless
+    /// [FixedIndexVec::set_auto_shrink] is enabled, in which case trimming might release it.
+    pub fn remove_reserved_pos(&mut self, reserved_pos: Idx) -> bool {
+        let position = reserved_pos.into_usize();
+        if position >= self.values.len() || !self.values[position].is_reserved() { return false; }
+        self.values[position] = Empty;
         self.reserved_spaces -= 1;
+        self.register_vacancy(reserved_pos);
         true
     }
 
+    /// Makes sure this index is reserved, reserving it if its position was empty or beyond the end
+    /// of the Vec, growing it as needed, or leaving it as is if it was already reserved, returning
+    /// an error only if the index holds a value.
+    #[allow(clippy::result_unit_err)]
+    pub fn ensure_reserved(&mut self, index: Idx) -> Result<(), ()> {
+        let position = index.into_usize();
+        if position < self.values.len() && self.values[position].is_reserved() { return Ok(()); }
+        if !self.claim_empty_pos(index) { return Err(()); }
+        self.values[position] = Reserved;
+        self.reserved_spaces += 1;
+        Ok(())
+    }
+
+    /// Reserves an index as [FixedIndexVec::reserve_pos] does, returning a [ReservationGuard] that
+    /// cancels the reservation through [FixedIndexVec::remove_reserved_pos] when dropped, unless a
+    /// value was stored on it through [ReservationGuard::fill].
+    pub fn reserve_guard(&mut self) -> ReservationGuard<'_, Value, Idx> {
+        let index = self.reserve_pos();
+        ReservationGuard { fixed_index_vec: self, index, filled: false }
+    }
+
+    /// Removes the reserved positions found from the right end bound up to the first position that
+    /// isn't reserved, returning how many of them were removed, then performs
+    /// [FixedIndexVec::clean_right] over any empty position this leaves on the right end.
+    /// <br>
+    /// <br>
+    /// Note the indexes of these reservations are no longer reserved, so values can't be pushed
+    /// into them through [FixedIndexVec::push_reserved].
+    pub fn trim_reserved_tail(&mut self) -> usize {
+        let trailing_reserved = self.trailing_reserved();
+        let first_position_to_remove = self.values.len() - trailing_reserved;
+        self.values.truncate(first_position_to_remove);
+        (first_position_to_remove..first_position_to_remove + trailing_reserved).for_each(|position| self.bump_generation(position));
+        self.reserved_spaces -= trailing_reserved;
+        self.clean_right();
+        self.debug_verify_invariants();
+        trailing_reserved
+    }
+
+    /// Rebuilds which positions are known to be empty or reserved by scanning every position, and
+    /// then performs [FixedIndexVec::clean_right], restoring a consistent state after positions
+    /// were changed directly, such as through [FixedIndexVec::iter_all_mut].
+    pub fn recompute_bookkeeping(&mut self) {
+        self.vacancies = self.values.iter()
+            .enumerate()
+            .filter(|(_, pos)| pos.is_empty())
+            .map(|(position, _)| Self::index_of(position))
+            .collect();
+        self.reserved_spaces = self.values.iter().filter(|pos| pos.is_reserved()).count();
+        self.clean_right();
+        self.debug_verify_invariants();
+    }
+
     /// Clears all the empty values found from the right end bound up to the first value it finds
     /// that is not an empty position, having a worst-case scenario of O(n) if all the values are
     /// empty.
+    /// <br>
+    /// <br>
+    /// If enabled through [FixedIndexVec::set_auto_shrink], it also releases unused memory.
     pub fn clean_right(&mut self) {
-        let leading_empty_poses = self.values.iter().rev().take_while(|pos| pos.is_empty()).count();
+        let leading_empty_poses = self.trailing_empty();
         if leading_empty_poses == 0 { return; }
         let first_index_to_remove = self.values.len() - leading_empty_poses;
         for _ in 0..leading_empty_poses {
             self.values.swap_remove(first_index_to_remove);
         }
-        self.vacancies.retain(|vacant_index| vacant_index < &first_index_to_remove);
+        //Vacancies are sorted, so those of the trimmed positions are found at the back
+        let kept_vacancies = self.vacancies.partition_point(|vacant_index| vacant_index.into_usize() < first_index_to_remove);
+        self.vacancies.truncate(kept_vacancies);
+        if self.auto_shrink && self.values.capacity() > self.values.len() * 4 {
+            self.values.shrink_to_fit();
+        }
     }
 
     /// Clears all and every empty space on the Vec while trying to move the least amount of values
@@ -153,34 +743,561 @@ impl<Value> FixedIndexVec<Value> {
     /// spaces and the second is full of used spaces, although for most of standard use-cases, the
     /// operation is O(n), where n is the number of empty spaces instead of the length of the
     /// complete Vec.
-    pub fn compress(&mut self, save_results: bool) -> CompressResult {
+    /// <br>
+    /// <br>
+    /// Values are moved following a specified order, which is part of this method's contract, so
+    /// that the resulting [CompressResult] is stable and can be relied upon: the position with the
+    /// highest index holding a value or a reservation is always moved into the empty position with
+    /// the lowest index, repeating this until no empty position is left before a non-empty one, and
+    /// the moves are returned in the same order they were made.
+    #[doc(alias = "compress_deterministic")]
+    pub fn compress(&mut self, save_results: bool) -> CompressResult<Idx> {
+        self.compress_stats(save_results).0
+    }
+
+    /// Performs [FixedIndexVec::compress], moving the same values, but keeping their relative order,
+    /// so that values that were pushed last, having the highest indexes, end up on the highest
+    /// empty positions and next to each other as much as possible, rather than being scattered in
+    /// reverse order over the lowest ones.
+    /// <br>
+    /// <br>
+    /// This is done by pairing the values past the resulting length with the empty positions
+    /// before it, both in ascending order.
+    pub fn compress_locality(&mut self, save_results: bool) -> CompressResult<Idx> {
+        let final_len = self.values.len() - self.vacancies.len();
+        let holes = self.vacancies.iter().map(|vacant| vacant.into_usize()).take_while(|&vacant| vacant < final_len);
+        let sources = (final_len..self.values.len()).filter(|&position| !self.values[position].is_empty());
+        let moves = holes.zip(sources)
+            .map(|(hole, source)| (Self::index_of(source), Self::index_of(hole)))
+            .collect::<Vec<_>>();
+        moves.iter().for_each(|&(old_index, new_index)| {
+            self.values.swap(old_index.into_usize(), new_index.into_usize());
+            self.bump_generation(old_index.into_usize());
+        });
+        self.vacancies.clear();
+        self.clean_right();
+        self.debug_verify_invariants();
+        CompressResult(if save_results { moves } else { Vec::new() })
+    }
+
+    /// Performs [FixedIndexVec::compress] only if the function accepts every move it would make,
+    /// being called with each index that would change followed by its new index before moving any
+    /// value, otherwise this [FixedIndexVec] is left untouched and an error is returned.
+    #[allow(clippy::result_unit_err)]
+    pub fn try_compress<F: FnMut(Idx, Idx) -> bool>(&mut self, mut validate: F) -> Result<CompressResult<Idx>, ()> {
+        if !self.compress_preview().into_iter().all(|(old_index, new_index)| validate(old_index, new_index)) {
+            return Err(());
+        }
+        Ok(self.compress(true))
+    }
+
+    /// Performs [FixedIndexVec::compress] and then, if the capacity exceeds `max_capacity`, shrinks
+    /// it toward `max_capacity`, although never below the resulting length.
+    pub fn compress_and_cap(&mut self, max_capacity: usize, save_results: bool) -> CompressResult<Idx> {
+        let result = self.compress(save_results);
+        if self.values.capacity() > max_capacity {
+            self.values.shrink_to(max_capacity);
+        }
+        result
+    }
+
+    /// Performs [FixedIndexVec::compress] and returns a function translating any index from before
+    /// compressing into its current index, leaving indexes that weren't moved as they are, so it
+    /// can be kept to lazily translate indexes found later on.
+    /// <br>
+    /// <br>
+    /// Each translation is a binary search over the moved indexes.
+    pub fn compress_translator(&mut self) -> impl Fn(Idx) -> Idx {
+        let mut moves = self.compress(true).0;
+        moves.sort_unstable_by_key(|&(old_index, _)| old_index);
+        move |index| match moves.binary_search_by_key(&index, |&(old_index, _)| old_index) {
+            Ok(move_pos) => moves[move_pos].1,
+            Err(_) => index,
+        }
+    }
+
+    /// Brings this [FixedIndexVec] into a canonical layout by removing every empty position while
+    /// keeping the order of values and reservations, returning every index that changed followed by
+    /// its new index, so that two [FixedIndexVec] holding the same values in the same order compare
+    /// as equal regardless of how fragmented they were.
+    /// <br>
+    /// <br>
+    /// Note this only preserves the indexes of values if there were no empty positions before them.
+    pub fn normalize(&mut self) -> CompressResult<Idx> {
+        let mut moves = Vec::new();
+        let mut target = 0;
+        for position in 0..self.values.len() {
+            if self.values[position].is_empty() { continue; }
+            if position != target {
+                self.values.swap(position, target);
+                self.bump_generation(position);
+                moves.push((Self::index_of(position), Self::index_of(target)));
+            }
+            target += 1;
+        }
+        self.vacancies.clear();
+        self.clean_right();
+        self.debug_verify_invariants();
+        CompressResult(moves)
+    }
+
+    /// Returns a copy of this [FixedIndexVec] on the canonical layout of [FixedIndexVec::normalize].
+    pub fn normalized(&self) -> FixedIndexVec<Value, Idx> where Value: Clone {
+        let mut normalized = self.clone();
+        normalized.normalize();
+        normalized
+    }
+
+    /// Performs [FixedIndexVec::compress], reporting every move made and its completion to the
+    /// observer instead of returning them.
+    pub fn compress_observed(&mut self, observer: &impl CompactObserver<Idx>) {
+        let moves = self.compress_moving(|old_index, new_index| observer.on_move(old_index, new_index));
+        observer.on_complete(moves);
+    }
+
+    /// Performs [FixedIndexVec::compress] while also returning [CompressStats] describing how many
+    /// values were moved, how many positions were freed and the resulting length.
+    pub fn compress_stats(&mut self, save_results: bool) -> (CompressResult<Idx>, CompressStats) {
+        let initial_len = self.values.len();
+        let mut results = Vec::new();
+        let moves = self.compress_moving(|old_index, new_index| if save_results { results.push((old_index, new_index)); });
+        let stats = CompressStats {
+            moves,
+            reclaimed_slots: initial_len - self.values.len(),
+            final_len: self.values.len(),
+        };
+        (CompressResult(results), stats)
+    }
+
+    /// Performs the moves of [FixedIndexVec::compress] in a single pass, calling `on_move` with the
+    /// old and new index of every moved position as they are made, and returning how many there
+    /// were, so that callers only keep the moves they need.
+    fn compress_moving<F: FnMut(Idx, Idx)>(&mut self, mut on_move: F) -> usize {
+        let mut end_cursor = self.values.len() - self.trailing_empty();
+        let mut moves = 0;
+        let mut vacancies = mem::take(&mut self.vacancies);
+        for vacant in vacancies.iter().map(|vacant| vacant.into_usize()) {
+            if end_cursor <= vacant { break; }
+            end_cursor -= 1;
+            while end_cursor > vacant && self.values[end_cursor].is_empty() {
+                end_cursor -= 1;
+            }
+            if end_cursor <= vacant { break; }
+            self.values.swap(end_cursor, vacant);
+            self.bump_generation(end_cursor);
+            on_move(Self::index_of(end_cursor), Self::index_of(vacant));
+            moves += 1;
+        }
+        //Since all empty values where now left on the right end, then we can take them out in a go
+        vacancies.clear();
+        self.vacancies = vacancies;
+        self.clean_right();
+        self.debug_verify_invariants();
+        moves
+    }
+
+    /// Performs [FixedIndexVec::compress], but stopping once it moved `max_moves` values, leaving
+    /// the remaining empty positions in place, so that compressing very large Vecs can be done in
+    /// steps.
+    /// <br>
+    /// <br>
+    /// Only the positions involved in those moves are visited, making this operation
+    /// O(max_moves * log n), where n is the number of empty positions, plus trimming the empty
+    /// positions this leaves on the right end.
+    pub fn compress_bounded(&mut self, max_moves: usize, save_results: bool) -> CompressResult<Idx> {
+        let moves = self.compress_preview_from(0, max_moves);
+        moves.iter().for_each(|&(old_index, new_index)| {
+            self.values.swap(old_index.into_usize(), new_index.into_usize());
+            self.bump_generation(old_index.into_usize());
+        });
+        //Moves fill the lowest vacancies in order, while the moved values leave their positions empty
+        self.vacancies.drain(..moves.len());
+        for &(old_index, _) in moves.iter().rev() {
+            //Moved values come from the highest positions, so these are inserted near the back
+            let pos = self.vacancies.partition_point(|&vacant_index| vacant_index < old_index);
+            self.vacancies.insert(pos, old_index);
+            self.note_freed(&[old_index]);
+        }
+        self.clean_right();
+        CompressResult(if save_results { moves } else { Vec::new() })
+    }
+
+    /// Performs [FixedIndexVec::compress], but also moving reserved positions right after the
+    /// values, leaving a layout of values first and reservations next, so that reservations can
+    /// be filled or cancelled on a contiguous tail.
+    /// <br>
+    /// <br>
+    /// As [FixedIndexVec::compress] does, the returned [CompressResult] contains every value and
+    /// reservation that was moved, first the values and then the reservations, so that reserved
+    /// indexes can be translated and still be filled through [FixedIndexVec::push_reserved].
+    pub fn compress_reserved_tail(&mut self, save_results: bool) -> CompressResult<Idx> {
+        let used_end = self.used_spaces_len();
+        let reserved_end = used_end + self.reserved_spaces;
+        //Values past the used range take the place of the positions before it holding no value
+        let value_holes = (0..used_end).filter(|&position| !self.values[position].is_used()).collect::<Vec<_>>();
+        let value_sources = (used_end..self.values.len()).filter(|&position| self.values[position].is_used()).collect::<Vec<_>>();
+        //Reservations outside the reserved range take the place of the positions within it that aren't reserved
+        let reservation_sources = (0..used_end).chain(reserved_end..self.values.len())
+            .filter(|&position| self.values[position].is_reserved())
+            .collect::<Vec<_>>();
+        let reservation_holes = (used_end..reserved_end).filter(|&position| !self.values[position].is_reserved()).collect::<Vec<_>>();
+        reservation_sources.iter().for_each(|&source| {
+            self.values[source] = Empty;
+            self.bump_generation(source);
+        });
+        //Every value hole is empty now, so swapping leaves the value sources empty
+        value_holes.iter().zip(&value_sources).for_each(|(&hole, &source)| {
+            self.values.swap(hole, source);
+            self.bump_generation(source);
+        });
+        reservation_holes.iter().for_each(|&hole| self.values[hole] = Reserved);
+        self.vacancies.clear();
+        self.clean_right();
+        self.debug_verify_invariants();
+        if !save_results { return CompressResult(Vec::new()); }
+        let value_moves = value_sources.into_iter().zip(value_holes);
+        let reservation_moves = reservation_sources.into_iter().zip(reservation_holes);
+        CompressResult(value_moves.chain(reservation_moves)
+            .map(|(source, hole)| (Self::index_of(source), Self::index_of(hole)))
+            .collect())
+    }
+
+    /// Moves values as the plan says, where each move is a pair of an index holding a value
+    /// followed by the empty position it's moved into, applied in order, and then performs
+    /// [FixedIndexVec::clean_right], so that a compression made elsewhere can be reproduced.
+    /// <br>
+    /// <br>
+    /// If any move can't be applied, the plan is not applied at all and a [CompressPlanError]
+    /// describing the first invalid move is returned.
+    pub fn compress_with_plan(&mut self, plan: &[(Idx, Idx)]) -> Result<(), CompressPlanError<Idx>> {
+        for (move_index, &(source, target)) in plan.iter().enumerate() {
+            let (source_position, target_position) = (source.into_usize(), target.into_usize());
+            let error = if source_position >= self.values.len() || !self.values[source_position].is_used() {
+                Some(CompressPlanError::SourceNotUsed { move_index, source })
+            } else if target_position >= self.values.len() || !self.values[target_position].is_empty() {
+                Some(CompressPlanError::TargetNotEmpty { move_index, target })
+            } else {
+                None
+            };
+            if let Some(error) = error {
+                //Undoes every move that was already applied, leaving this FixedIndexVec untouched
+                plan[..move_index].iter().rev().for_each(|&(source, target)| self.values.swap(source.into_usize(), target.into_usize()));
+                return Err(error);
+            }
+            self.values.swap(source_position, target_position);
+        }
+        plan.iter().for_each(|&(source, _)| self.bump_generation(source.into_usize()));
+        self.vacancies = self.values.iter()
+            .enumerate()
+            .filter(|(_, pos)| pos.is_empty())
+            .map(|(position, _)| Self::index_of(position))
+            .collect();
         self.clean_right();
-        if self.vacancies.is_empty() { return CompressResult(Vec::new()); }
+        self.debug_verify_invariants();
+        Ok(())
+    }
+
+    /// Performs [FixedIndexVec::compress], but only over the positions whose indexes are `from` or
+    /// higher, so that values and empty positions with lower indexes are left untouched.
+    pub fn compress_suffix(&mut self, from: Idx, save_results: bool) -> CompressResult<Idx> {
+        let from = from.into_usize();
+        let moves = self.compress_preview_from(from, usize::MAX);
+        moves.iter().for_each(|&(old_index, new_index)| {
+            self.values.swap(old_index.into_usize(), new_index.into_usize());
+            self.bump_generation(old_index.into_usize());
+        });
+        //Every empty position on the suffix was now left on the right end, so they can be taken out
+        let suffix_vacancies = self.vacancies.partition_point(|vacant| vacant.into_usize() < from);
+        self.vacancies.truncate(suffix_vacancies);
+        self.clean_right();
+        self.debug_verify_invariants();
+        CompressResult(if save_results { moves } else { Vec::new() })
+    }
+
+    /// Returns every index that would change followed by its new index if
+    /// [FixedIndexVec::compress] was called, without modifying this [FixedIndexVec].
+    pub fn compress_preview(&self) -> Vec<(Idx, Idx)> {
+        self.compress_preview_from(0, usize::MAX)
+    }
+
+    /// Returns the moves [FixedIndexVec::compress_preview] would, but only filling empty positions
+    /// whose indexes are `from` or higher, and stopping once there are `max_moves` of them.
+    fn compress_preview_from(&self, from: usize, max_moves: usize) -> Vec<(Idx, Idx)> {
+        let leading_empty_poses = self.trailing_empty();
+        let mut end_cursor = self.values.len() - leading_empty_poses;
+        let mut moves = Vec::new();
+        let first_vacancy = self.vacancies.partition_point(|vacant| vacant.into_usize() < from);
+        for vacant in self.vacancies.range(first_vacancy..).map(|vacant| vacant.into_usize()) {
+            if moves.len() == max_moves || end_cursor <= vacant { break; }
+            end_cursor -= 1;
+            while end_cursor > vacant && self.values[end_cursor].is_empty() {
+                end_cursor -= 1;
+            }
+            if end_cursor <= vacant { break; }
+            moves.push((Self::index_of(end_cursor), Self::index_of(vacant)));
+        }
+        moves
+    }
+
+    /// Returns the indexes holding a value and clones of said values, both sorted by ascending index
+    /// so that each index is aligned with its value, without modifying this [FixedIndexVec].
+    pub fn to_dense(&self) -> (Vec<Idx>, Vec<Value>) where Value: Clone {
+        let mut indexes = Vec::with_capacity(self.used_spaces_len());
+        let mut values = Vec::with_capacity(self.used_spaces_len());
+        self.iter_index().for_each(|(index, value)| {
+            indexes.push(index);
+            values.push(value.clone());
+        });
+        (indexes, values)
+    }
+
+    /// Performs [FixedIndexVec::compress], but positions whose indexes are found in `keep` are
+    /// never moved nor filled, so the values they hold keep their indexes and the returned
+    /// [CompressResult] never includes them.
+    /// <br>
+    /// <br>
+    /// Note that as kept positions aren't moved, empty positions found before them might not get
+    /// filled, as there might be no other value after them to take their place.
+    pub fn compress_avoiding(&mut self, keep: &[Idx], save_results: bool) -> CompressResult<Idx> {
+        self.clean_right();
+        //Sorted so each lookup is a binary search rather than a scan over every kept index
+        let mut keep = keep.to_vec();
+        keep.sort_unstable();
+        keep.dedup();
         let mut index_results = Vec::new();
+        let mut new_vacancies = Vec::new();
+        let mut freed_indexes = Vec::new();
         let mut end_cursor = self.values.len();
         mem::take(&mut self.vacancies).into_iter().for_each(|vacant| {
-            if end_cursor <= vacant { return; }
-            end_cursor -= 1;
-            if end_cursor <= vacant { return; }
-            while self.values[end_cursor].is_empty() {
-                end_cursor -= 1;
-                if end_cursor <= vacant { return; }
+            let mut source = None;
+            if keep.binary_search(&vacant).is_err() {
+                while source.is_none() && end_cursor > vacant.into_usize() + 1 {
+                    end_cursor -= 1;
+                    let end_index = Self::index_of(end_cursor);
+                    if !self.values[end_cursor].is_empty() && keep.binary_search(&end_index).is_err() {
+                        source = Some(end_index);
+                    }
+                }
             }
-            self.values.swap(vacant, end_cursor);
-            if save_results {
-                index_results.push((end_cursor, vacant));
+            match source {
+                Some(source) => {
+                    self.values.swap(vacant.into_usize(), source.into_usize());
+                    self.bump_generation(source.into_usize());
+                    new_vacancies.push(source);
+                    freed_indexes.push(source);
+                    if save_results {
+                        index_results.push((source, vacant));
+                    }
+                }
+                None => new_vacancies.push(vacant),
             }
         });
-        //Since all empty values where now left on the right end, then we can take them out in a go
+        new_vacancies.sort_unstable();
+        self.vacancies = new_vacancies.into();
+        self.note_freed(&freed_indexes);
         self.clean_right();
+        self.debug_verify_invariants();
         CompressResult(index_results)
     }
 
+    /// Removes every value found on these indexes and compresses the Vec afterward, returning the
+    /// removed values along every index that changed followed by its new index, as
+    /// [FixedIndexVec::compress] would.
+    /// <br>
+    /// <br>
+    /// The returned changes are the net result of all removals rather than one per removal, each
+    /// moved value appears once with its index before calling this method and its final index, and
+    /// only values beyond the final length are moved.
+    /// <br>
+    /// <br>
+    /// Note the compression fills every empty position, including those that were already empty
+    /// before calling this method, and indexes holding no value are ignored.
+    pub fn swap_compact_remove_many(&mut self, indices: impl IntoIterator<Item=Idx>) -> (Vec<Value>, Vec<(Idx, Idx)>) {
+        let mut removed_values = Vec::new();
+        let mut guard = VacancyGuard::new(self);
+        for index in indices {
+            if !guard.fixed_index_vec.contains_index(index) { continue; }
+            removed_values.push(guard.take(index.into_usize()));
+        }
+        drop(guard);
+        (removed_values, self.compress(true).0)
+    }
+
+    /// Removes every value for which the predicate returns false, keeping their indexes as
+    /// vacancies as [FixedIndexVec::remove] would, but registering all of them on a single O(n)
+    /// pass rather than searching where each one goes.
+    /// <br>
+    /// <br>
+    /// Freed indexes are kept sorted along the previous empty positions, so they are reused in the
+    /// same order as if they were removed one by one, following the [ReusePolicy].
+    /// <br>
+    /// <br>
+    /// If the predicate panics, the values removed until then stay removed and their positions are
+    /// registered as empty, while the remaining values are left in place.
+    pub fn retain<F: FnMut(Idx, &Value) -> bool>(&mut self, mut pred: F) {
+        let mut guard = VacancyGuard::new(self);
+        for position in 0..guard.fixed_index_vec.values.len() {
+            let keep = match &guard.fixed_index_vec.values[position] {
+                Used(value) => pred(Self::index_of(position), value),
+                _ => true,
+            };
+            if keep { continue; }
+            drop(guard.take(position));
+        }
+        drop(guard);
+        self.debug_verify_invariants();
+    }
+
+    /// Removes every value equal to this one, as [FixedIndexVec::retain] would, returning the indexes
+    /// they were found on sorted ascending.
+    pub fn remove_all_eq(&mut self, value: &Value) -> Vec<Idx> where Value: PartialEq {
+        let mut freed_indexes = Vec::new();
+        self.retain(|index, current| {
+            if current != value { return true; }
+            freed_indexes.push(index);
+            false
+        });
+        freed_indexes
+    }
+
+    /// Performs [FixedIndexVec::retain] and then, if the fraction of empty positions over
+    /// [FixedIndexVec::len] exceeds the threshold, performs [FixedIndexVec::compress], returning
+    /// its [CompressResult] so that moved values can still be found.
+    pub fn retain_and_maybe_compress<F: FnMut(Idx, &Value) -> bool>(&mut self, pred: F, fragmentation_threshold: f32) -> Option<CompressResult<Idx>> {
+        self.retain(pred);
+        if self.values.is_empty() { return None; }
+        let fragmentation = self.vacancies.len() as f32 / self.values.len() as f32;
+        if fragmentation <= fragmentation_threshold { return None; }
+        Some(self.compress(true))
+    }
+
+    /// Moves every value for which the predicate returns false into the other [FixedIndexVec] by
+    /// pushing them, returning every index each value had followed by the index it got on `dest`.
+    /// <br>
+    /// <br>
+    /// As with [FixedIndexVec::retain], if the predicate panics, the values moved until then stay on
+    /// `dest` and their positions are registered as empty.
+    pub fn retain_into<F: FnMut(Idx, &Value) -> bool>(&mut self, dest: &mut FixedIndexVec<Value, Idx>, mut pred: F) -> Vec<(Idx, Idx)> {
+        let mut index_results = Vec::new();
+        let mut guard = VacancyGuard::new(self);
+        for position in 0..guard.fixed_index_vec.values.len() {
+            let index = Self::index_of(position);
+            let keep = match &guard.fixed_index_vec.values[position] {
+                Used(value) => pred(index, value),
+                _ => true,
+            };
+            if keep { continue; }
+            let value = guard.take(position);
+            index_results.push((index, dest.push(value)));
+        }
+        drop(guard);
+        self.debug_verify_invariants();
+        index_results
+    }
+
+    /// Lazily removes every value for which the predicate returns true, yielding them along their
+    /// indexes, values for which the predicate returns false are left in place.
+    /// <br>
+    /// <br>
+    /// Values are removed as the iterator advances, so dropping it early leaves the values it
+    /// didn't reach in place, once dropped, [FixedIndexVec::clean_right] is performed.
+    pub fn drain_filter<F: FnMut(Idx, &mut Value) -> bool>(&mut self, pred: F) -> DrainFilter<'_, Value, Idx, F> {
+        DrainFilter { guard: VacancyGuard::new(self), position: 0, pred }
+    }
+
+    /// Registers these indexes of positions that were just emptied as vacancies, the indexes must
+    /// be sorted ascending, as they are merged with the current vacancies on a single O(n) pass.
+    fn merge_vacancies(&mut self, freed: Vec<Idx>) {
+        if freed.is_empty() { return; }
+        let mut previous_vacancies = mem::take(&mut self.vacancies).into_iter().peekable();
+        let mut freed_indexes = freed.iter().copied().peekable();
+        let mut vacancies = VecDeque::with_capacity(previous_vacancies.len() + freed_indexes.len());
+        loop {
+            let next = match (previous_vacancies.peek(), freed_indexes.peek()) {
+                (Some(previous), Some(freed)) if previous < freed => previous_vacancies.next(),
+                (_, Some(_)) => freed_indexes.next(),
+                (Some(_), None) => previous_vacancies.next(),
+                (None, None) => break,
+            };
+            vacancies.push_back(next.unwrap());
+        }
+        self.vacancies = vacancies;
+        self.note_freed(&freed);
+    }
+
+    /// Removes every value, returning the indexes that held them sorted ascending, while reserved
+    /// positions are left in place, unlike [FixedIndexVec::clear], which also removes them.
+    pub fn drain_used_indices(&mut self) -> Vec<Idx> {
+        let used_spaces = self.used_spaces_len();
+        let mut guard = VacancyGuard::new(self);
+        guard.freed_indexes.reserve_exact(used_spaces);
+        for position in 0..guard.fixed_index_vec.values.len() {
+            if !guard.fixed_index_vec.values[position].is_used() { continue; }
+            drop(guard.take(position));
+        }
+        let freed_indexes = guard.freed_indexes.clone();
+        drop(guard);
+        self.debug_verify_invariants();
+        freed_indexes
+    }
+
+    /// Splits this [FixedIndexVec] in two, leaving only the positions whose indexes are below `at`,
+    /// and returning a new [FixedIndexVec] with the remaining positions, whose indexes are moved down
+    /// by `at`, along every index of a value or reservation that changed followed by its new index.
+    /// <br>
+    /// <br>
+    /// When `at` is 0, every position is moved into the new [FixedIndexVec] keeping its index, so no
+    /// index is returned.
+    pub fn split_at(&mut self, at: Idx) -> (FixedIndexVec<Value, Idx>, Vec<(Idx, Idx)>) {
+        let at_position = at.into_usize();
+        let mut high = Self::with_index_type();
+        if at_position >= self.values.len() { return (high, Vec::new()); }
+        let high_positions = self.values.split_off(at_position);
+        (at_position..at_position + high_positions.len()).for_each(|position| self.bump_generation(position));
+        self.reserved_spaces -= high_positions.iter().filter(|pos| pos.is_reserved()).count();
+        let low_vacancies = self.vacancies.partition_point(|vacant| vacant.into_usize() < at_position);
+        self.vacancies.truncate(low_vacancies);
+        self.clean_right();
+        let index_results = high_positions.iter()
+            .enumerate()
+            .filter(|(_, pos)| at_position > 0 && !pos.is_empty())
+            .map(|(position, _)| (Self::index_of(at_position + position), Self::index_of(position)))
+            .collect();
+        high.extend_positions(high_positions);
+        (high, index_results)
+    }
+
+    /// Swaps the contents of both [FixedIndexVec], keeping every index, and returns the lengths
+    /// they had before swapping, being the first one the length of this [FixedIndexVec].
+    pub fn swap_with(&mut self, other: &mut Self) -> (usize, usize) {
+        let lengths = (self.len(), other.len());
+        mem::swap(self, other);
+        lengths
+    }
+
+    /// Clears all positions as [FixedIndexVec::clear] does, keeping the allocated capacity so the
+    /// Vec can be filled again without reallocating, and advancing the [FixedIndexVec::generation]
+    /// of every position, so that [FixedIndexVec::get_checked] rejects indexes from before the
+    /// reset on debug builds.
+    pub fn reset(&mut self) {
+        self.clear();
+    }
+
     /// Clears all positions, whether they are used, reserved or empty, leaving it completely empty.
     pub fn clear(&mut self) {
-        self.values.clear();
+        #[cfg(debug_assertions)]
+        {
+            //Generations are only tracked once a position is emptied, so cleared positions are added
+            if self.generations.len() < self.values.len() {
+                self.generations.resize(self.values.len(), 0);
+            }
+            self.generations.iter_mut().for_each(|generation| *generation = generation.wrapping_add(1));
+        }
+        //Positions are cleared last, so that the Vec is left empty and consistent even if a value's drop panics
         self.vacancies.clear();
+        self.recently_freed.clear();
         self.reserved_spaces = 0;
+        self.values.clear();
     }
 
     /// Returns the amount of spaces used, note this is not the same as the amount of **Used**
@@ -194,10 +1311,41 @@ impl<Value> FixedIndexVec<Value> {
     /// <br>
     /// If you want to use iterate over used spaces, use [FixedIndexVec::iter],
     /// [FixedIndexVec::iter_mut] and [FixedIndexVec::into_iter].
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.values.len()
     }
 
+    /// Returns the highest index of any position, whether used, reserved or empty, being
+    /// [FixedIndexVec::len] minus one, or [Option::None] if there are no positions.
+    pub fn max_index(&self) -> Option<Idx> {
+        self.values.len().checked_sub(1).map(Self::index_of)
+    }
+
+    /// Returns whether there are no positions at all, meaning [FixedIndexVec::len] is 0, unlike
+    /// checking [FixedIndexVec::used_spaces_len], which would also be 0 when only reserved positions
+    /// are left.
+    pub fn has_no_slots(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the position matching this index, or [Pos::Empty] if it's beyond the end of the Vec,
+    /// as if the Vec was endlessly followed by empty positions.
+    pub fn pos_or_empty(&self, index: Idx) -> &Pos<Value> {
+        self.values.get(index.into_usize()).unwrap_or(const { &Empty })
+    }
+
+    /// Returns every position as a slice, including empty and reserved positions, where the index
+    /// of each position matches the index of the value it holds.
+    pub fn as_positions(&self) -> &[Pos<Value>] {
+        &self.values
+    }
+
+    /// Returns the amount of positions the Vec can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
     /// Amount of positions holding a value.
     pub fn used_spaces_len(&self) -> usize {
         self.values.len() - (self.reserved_spaces_len() + self.empty_spaces_len())
@@ -213,68 +1361,407 @@ impl<Value> FixedIndexVec<Value> {
         self.vacancies.len()
     }
 
+    /// Amount of empty positions found from the right end bound up to the first position that
+    /// isn't empty, which is usually 0, as removing values performs [FixedIndexVec::clean_right].
+    pub fn trailing_empty(&self) -> usize {
+        self.values.iter().rev().take_while(|pos| pos.is_empty()).count()
+    }
+
+    /// Amount of reserved positions found from the right end bound up to the first position that
+    /// isn't reserved, which can be removed through [FixedIndexVec::trim_reserved_tail].
+    pub fn trailing_reserved(&self) -> usize {
+        self.values.iter().rev().take_while(|pos| pos.is_reserved()).count()
+    }
+
+    /// Amount of empty positions whose indexes are within this range, found through a binary search
+    /// over the sorted vacancies, making this operation O(log n), where n is the number of empty
+    /// positions.
+    pub fn vacancies_in(&self, range: Range<Idx>) -> usize {
+        if range.start >= range.end { return 0; }
+        let start = self.vacancies.partition_point(|&vacant_index| vacant_index < range.start);
+        let end = self.vacancies.partition_point(|&vacant_index| vacant_index < range.end);
+        end - start
+    }
+
+    /// Returns whether every position holds a value, having no empty nor reserved positions, in
+    /// which case the indexes of the values are guaranteed to be exactly `0..len()`, meaning they
+    /// can be accessed directly without checking which indexes hold values.
+    pub fn is_packed(&self) -> bool {
+        self.vacancies.is_empty() && self.reserved_spaces == 0
+    }
+
+    /// Returns a bitmap telling which indexes hold a value, where the index i is represented by the
+    /// bit `i % 64` of the word `i / 64`, counting bits from the least significant one, so that
+    /// checking many indexes against the same state doesn't require visiting the positions again.
+    /// <br>
+    /// <br>
+    /// There are as many words as needed to cover [FixedIndexVec::len], the bits past it are unset.
+    pub fn occupancy_bitset(&self) -> Vec<u64> {
+        let mut bitset = alloc::vec![0u64; self.values.len().div_ceil(64)];
+        self.values.iter()
+            .enumerate()
+            .filter(|(_, pos)| pos.is_used())
+            .for_each(|(position, _)| bitset[position / 64] |= 1 << (position % 64));
+        bitset
+    }
+
+    /// Iterator over the indexes holding a value on this [FixedIndexVec] but not on the other one,
+    /// comparing indexes only, so their values can be of different types.
+    pub fn index_difference<'a, V2>(&'a self, other: &'a FixedIndexVec<V2, Idx>) -> impl Iterator<Item=Idx> + 'a {
+        self.iter_index()
+            .map(|(index, _)| index)
+            .filter(move |&index| !other.contains_index(index))
+    }
+
+    /// Iterator over the indexes holding a value on both this [FixedIndexVec] and the other one,
+    /// comparing indexes only, so their values can be of different types.
+    pub fn index_intersection<'a, V2>(&'a self, other: &'a FixedIndexVec<V2, Idx>) -> impl Iterator<Item=Idx> + 'a {
+        self.iter_index()
+            .map(|(index, _)| index)
+            .filter(move |&index| other.contains_index(index))
+    }
+
     /// Returns whether this index holds a value or not.
-    pub fn contains_index(&self, index: usize) -> bool {
-        index < self.values.len() && self.values[index].is_used()
+    pub fn contains_index(&self, index: Idx) -> bool {
+        let position = index.into_usize();
+        position < self.values.len() && self.values[position].is_used()
+    }
+
+    /// Iterator over the indexes that hold a value out of these ones, in the same order, skipping
+    /// those that are empty, reserved or out of bounds.
+    pub fn filter_present<'a>(&'a self, indices: impl IntoIterator<Item=Idx> + 'a) -> impl Iterator<Item=Idx> + 'a {
+        indices.into_iter().filter(|&index| self.contains_index(index))
+    }
+
+    /// Returns how many times the position of this index was emptied, letting
+    /// [FixedIndexVec::get_checked] tell whether an index refers to a value that was removed even
+    /// if another value took its position afterward.
+    /// <br>
+    /// <br>
+    /// Generations are only kept on debug builds, on release builds this always returns 0.
+    pub fn generation(&self, index: Idx) -> u32 {
+        #[cfg(debug_assertions)]
+        return self.generations.get(index.into_usize()).copied().unwrap_or(0);
+        #[cfg(not(debug_assertions))]
+        {
+            let _ = index;
+            0
+        }
+    }
+
+    /// Returns a reference to the value matching this index as [FixedIndexVec::get] does, but on
+    /// debug builds, it also returns [Option::None] when the position isn't on the expected
+    /// [FixedIndexVec::generation], meaning the value that index referred to was removed.
+    /// <br>
+    /// <br>
+    /// Generations are only kept on debug builds, on release builds this is the same as
+    /// [FixedIndexVec::get].
+    pub fn get_checked(&self, index: Idx, expected_generation: u32) -> Option<&Value> {
+        #[cfg(debug_assertions)]
+        if self.generation(index) != expected_generation { return None; }
+        #[cfg(not(debug_assertions))]
+        let _ = expected_generation;
+        self.get(index)
+    }
+
+    /// Marks that this position was emptied by advancing its generation, this is only done on debug
+    /// builds.
+    fn bump_generation(&mut self, position: usize) {
+        #[cfg(debug_assertions)]
+        {
+            if self.generations.len() <= position {
+                self.generations.resize(position + 1, 0);
+            }
+            self.generations[position] = self.generations[position].wrapping_add(1);
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = position;
     }
 
     /// Returns a reference to the value matching this index.
-    pub fn get(&self, index: usize) -> Option<&Value> {
+    pub fn get(&self, index: Idx) -> Option<&Value> {
         if !self.contains_index(index) { return None; }
-        self.values[index].as_opt_ref()
+        self.values[index.into_usize()].as_opt_ref()
     }
 
     /// Returns a mutable reference to the value matching this index.
-    pub fn get_mut(&mut self, index: usize) -> Option<&mut Value> {
+    pub fn get_mut(&mut self, index: Idx) -> Option<&mut Value> {
         if !self.contains_index(index) { return None; }
-        self.values[index].as_opt_mut()
+        self.values[index.into_usize()].as_opt_mut()
     }
 
-    /// Iterator referencing all stored value (This excludes empty and reserved positions).
-    pub fn iter(&self) -> impl Iterator<Item=&Value> {
+    /// Returns a mutable reference to the value matching this index as [FixedIndexVec::get_mut]
+    /// does, but if the position right before it is empty, the value is first moved into it,
+    /// compressing that gap, returning the index the value ends on along the reference.
+    /// <br>
+    /// <br>
+    /// Note this changes the index of the value whenever it's moved, leaving its old index empty.
+    pub fn get_mut_compacting(&mut self, index: Idx) -> Option<(Idx, &mut Value)> {
+        if !self.contains_index(index) { return None; }
+        let mut position = index.into_usize();
+        if position > 0 && self.values[position - 1].is_empty() {
+            //No vacancy lies between both positions, so the moved one takes the same place in order
+            let vacancy_pos = self.vacancies.binary_search(&Self::index_of(position - 1)).unwrap();
+            self.vacancies[vacancy_pos] = index;
+            self.note_freed(&[index]);
+            self.values.swap(position - 1, position);
+            self.bump_generation(position);
+            position -= 1;
+            self.clean_right();
+        }
+        Some((Self::index_of(position), self.values[position].as_opt_mut().unwrap()))
+    }
+
+    /// Returns mutable references to the values matching both indexes along said indexes, as long
+    /// as both indexes are different and hold a value.
+    #[allow(clippy::type_complexity)]
+    pub fn get2_mut_indexed(&mut self, a: Idx, b: Idx) -> Option<((Idx, &mut Value), (Idx, &mut Value))> {
+        if a == b || !self.contains_index(a) || !self.contains_index(b) { return None; }
+        let (a_position, b_position) = (a.into_usize(), b.into_usize());
+        let (left, right) = self.values.split_at_mut(a_position.max(b_position));
+        let lower_value = left[a_position.min(b_position)].as_opt_mut()?;
+        let higher_value = right[0].as_opt_mut()?;
+        if a_position < b_position {
+            Some(((a, lower_value), (b, higher_value)))
+        } else {
+            Some(((a, higher_value), (b, lower_value)))
+        }
+    }
+
+    /// Returns mutable references to the values matching each of these indexes in the same order,
+    /// or a [GetManyError] telling the first index that is out of bounds, holds no value or is
+    /// repeated.
+    pub fn get_many_mut<const N: usize>(&mut self, indices: [Idx; N]) -> Result<[&mut Value; N], GetManyError<Idx>> {
+        for (nth, &index) in indices.iter().enumerate() {
+            let position = index.into_usize();
+            if position >= self.values.len() { return Err(GetManyError::OutOfBounds(index)); }
+            if !self.values[position].is_used() { return Err(GetManyError::NotPresent(index)); }
+            if indices[..nth].contains(&index) { return Err(GetManyError::Duplicate(index)); }
+        }
+        let positions = self.values.get_disjoint_mut(indices.map(Idx::into_usize)).ok().unwrap();
+        Ok(positions.map(|pos| pos.as_opt_mut().unwrap()))
+    }
+
+    /// Iterator referencing the values matching each of these indexes in the same order, yielding
+    /// [Option::None] for those indexes holding no value.
+    pub fn values_at<'a>(&'a self, indices: &'a [Idx]) -> impl Iterator<Item=Option<&'a Value>> + 'a {
+        indices.iter().map(|&index| self.get(index))
+    }
+
+    /// Returns a clone of the value matching this index.
+    pub fn get_cloned(&self, index: Idx) -> Option<Value> where Value: Clone {
+        match self.values.get(index.into_usize()) {
+            Some(Used(value)) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Creates a FixedIndexVec holding clones of the values found on these indexes, keeping their
+    /// indexes, while every other position is left empty, indexes holding no value are ignored.
+    pub fn clone_subset(&self, indices: impl IntoIterator<Item=Idx>) -> FixedIndexVec<Value, Idx> where Value: Clone {
+        let mut subset = Self::with_index_type();
+        for index in indices {
+            let Some(value) = self.get(index) else { continue; };
+            if subset.claim_empty_pos(index) {
+                subset.values[index.into_usize()] = Used(value.clone());
+            }
+        }
+        subset
+    }
+
+    /// Returns the lowest index holding a value along a reference to said value.
+    pub fn first(&self) -> Option<(Idx, &Value)> {
+        self.iter_index().next()
+    }
+
+    /// Returns the highest index holding a value along a reference to said value, skipping any
+    /// trailing reserved position.
+    pub fn last(&self) -> Option<(Idx, &Value)> {
         self.values.iter()
-            .filter(|pos| pos.is_used())
-            .map(|pos| pos.as_opt_ref().unwrap())
+            .enumerate()
+            .rev()
+            .find_map(|(position, pos)| Some((Self::index_of(position), pos.as_opt_ref()?)))
+    }
+
+    /// Returns the index holding the value for which the function returns the greatest key, being
+    /// the lowest of those indexes if several values share it.
+    pub fn position_of_max_by_key<K: Ord, F: FnMut(&Value) -> K>(&self, mut f: F) -> Option<Idx> {
+        self.iter_index()
+            .map(|(index, value)| (index, f(value)))
+            .reduce(|best, candidate| if candidate.1 > best.1 { candidate } else { best })
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the index holding the value for which the function returns the least key, being the
+    /// lowest of those indexes if several values share it.
+    pub fn position_of_min_by_key<K: Ord, F: FnMut(&Value) -> K>(&self, mut f: F) -> Option<Idx> {
+        self.iter_index()
+            .map(|(index, value)| (index, f(value)))
+            .reduce(|best, candidate| if candidate.1 < best.1 { candidate } else { best })
+            .map(|(index, _)| index)
+    }
+
+    /// Returns a reference to the value matching this index without checking whether this index
+    /// holds a value.
+    /// <br>
+    /// <br>
+    /// # Safety
+    /// The index must be in bounds and its position must be [Pos::Used], calling this method on an
+    /// out of bounds index or on an empty or reserved position is undefined behaviour, even if the
+    /// returned reference is never used. On debug builds, misuse panics instead.
+    pub unsafe fn get_unchecked(&self, index: Idx) -> &Value {
+        debug_assert!(self.contains_index(index), "FixedIndexVec::get_unchecked on index {} which holds no value", index.into_usize());
+        match self.values.get_unchecked(index.into_usize()) {
+            Used(value) => value,
+            _ => core::hint::unreachable_unchecked(),
+        }
+    }
+
+    /// Returns a mutable reference to the value matching this index without checking whether this
+    /// index holds a value.
+    /// <br>
+    /// <br>
+    /// # Safety
+    /// The index must be in bounds and its position must be [Pos::Used], calling this method on an
+    /// out of bounds index or on an empty or reserved position is undefined behaviour, even if the
+    /// returned reference is never used. On debug builds, misuse panics instead.
+    pub unsafe fn get_unchecked_mut(&mut self, index: Idx) -> &mut Value {
+        debug_assert!(self.contains_index(index), "FixedIndexVec::get_unchecked_mut on index {} which holds no value", index.into_usize());
+        match self.values.get_unchecked_mut(index.into_usize()) {
+            Used(value) => value,
+            _ => core::hint::unreachable_unchecked(),
+        }
+    }
+
+    /// Iterator referencing all stored value (This excludes empty and reserved positions), yielding
+    /// them by ascending index, as [FixedIndexVec::iter_ordered] guarantees.
+    /// <br>
+    /// <br>
+    /// Its length is known from [FixedIndexVec::used_spaces_len], making it an [ExactSizeIterator].
+    pub fn iter(&self) -> Iter<'_, Value> {
+        Iter { remaining: self.used_spaces_len(), positions: self.values.iter() }
+    }
+
+    /// Iterator referencing all stored value (This excludes empty and reserved positions), which
+    /// is guaranteed to yield them by ascending index.
+    pub fn iter_ordered(&self) -> impl Iterator<Item=&Value> {
+        self.iter()
     }
 
     /// Iterator referencing all stored value (This excludes empty and reserved positions) and their
     /// indexes.
-    pub fn iter_index(&self) -> impl Iterator<Item=(usize, &Value)> {
+    /// <br>
+    /// <br>
+    /// The iterator can be cloned to walk the remaining values more than once.
+    pub fn iter_index(&self) -> impl Iterator<Item=(Idx, &Value)> + Clone {
+        self.values.iter()
+            .enumerate()
+            .filter(|(_, pos)| pos.is_used())
+            .map(|(position, pos)| (Self::index_of(position), pos.as_opt_ref().unwrap()))
+    }
+
+    /// Iterator referencing the values whose indexes are within the range and their indexes, bounds
+    /// past the end of the Vec are clamped to its length.
+    pub fn range(&self, range: Range<Idx>) -> impl Iterator<Item=(Idx, &Value)> + '_ {
+        let end = range.end.into_usize().min(self.values.len());
+        let start = range.start.into_usize().min(end);
+        self.values[start..end].iter()
+            .enumerate()
+            .filter_map(move |(offset, pos)| Some((Self::index_of(start + offset), pos.as_opt_ref()?)))
+    }
+
+    /// Iterator over the indexes holding a value, from the highest to the lowest, walking the
+    /// positions from the back rather than collecting and reversing them.
+    pub fn indices_rev(&self) -> impl Iterator<Item=Idx> + '_ {
         self.values.iter()
             .enumerate()
+            .rev()
             .filter(|(_, pos)| pos.is_used())
-            .map(|(index, pos)| (index, pos.as_opt_ref().unwrap()))
+            .map(|(position, _)| Self::index_of(position))
     }
 
     /// Mutable iterator over all stored value (This excludes empty and reserved positions) and
     /// their indexes.
-    pub fn iter_mut(&mut self) -> impl Iterator<Item=&mut Value> {
-        self.values.iter_mut()
-            .filter(|pos| pos.is_used())
-            .map(|pos| pos.as_opt_mut().unwrap())
+    /// <br>
+    /// <br>
+    /// Its length is known from [FixedIndexVec::used_spaces_len], making it an [ExactSizeIterator].
+    pub fn iter_mut(&mut self) -> IterMut<'_, Value> {
+        IterMut { remaining: self.used_spaces_len(), positions: self.values.iter_mut() }
     }
 
     /// Mutable iterator over all stored value (This excludes empty and reserved positions).
-    pub fn iter_index_mut(&mut self) -> impl Iterator<Item=(usize, &mut Value)> {
+    pub fn iter_index_mut(&mut self) -> impl Iterator<Item=(Idx, &mut Value)> {
         self.values.iter_mut()
             .enumerate()
             .filter(|(_, pos)| pos.is_used())
-            .map(|(index, pos)| (index, pos.as_opt_mut().unwrap()))
+            .map(|(position, pos)| (Self::index_of(position), pos.as_opt_mut().unwrap()))
+    }
+
+    /// Folds every stored value (This excludes empty and reserved positions) and its index into an
+    /// accumulator, visiting them by ascending index.
+    pub fn fold_indexed<B, F: FnMut(B, Idx, &Value) -> B>(&self, init: B, mut f: F) -> B {
+        self.values.iter()
+            .enumerate()
+            .fold(init, |accumulator, (position, pos)| match pos {
+                Used(value) => f(accumulator, Self::index_of(position), value),
+                _ => accumulator,
+            })
+    }
+
+    /// Calls the function with every stored value (This excludes empty and reserved positions) and
+    /// its index, walking the positions directly, so it never allocates.
+    pub fn visit_used<F: FnMut(Idx, &Value)>(&self, mut f: F) {
+        for (position, pos) in self.values.iter().enumerate() {
+            if let Used(value) = pos { f(Self::index_of(position), value); }
+        }
+    }
+
+    /// Calls the function with a mutable reference to every stored value (This excludes empty and
+    /// reserved positions) and its index, walking the positions directly, so it never allocates.
+    pub fn visit_used_mut<F: FnMut(Idx, &mut Value)>(&mut self, mut f: F) {
+        for (position, pos) in self.values.iter_mut().enumerate() {
+            if let Used(value) = pos { f(Self::index_of(position), value); }
+        }
+    }
+
+    /// Calls the function with every stored value (This excludes empty and reserved positions) and
+    /// its index, letting the function mutate them while borrowing other data mutably.
+    pub fn for_each_indexed_mut<F: FnMut(Idx, &mut Value)>(&mut self, mut f: F) {
+        self.iter_index_mut().for_each(|(index, value)| f(index, value));
+    }
+
+    /// Mutable iterator over every position, whether used, reserved or empty, and their indexes.
+    /// <br>
+    /// <br>
+    /// Note that changing the variant of a position isn't tracked, so
+    /// [FixedIndexVec::recompute_bookkeeping] must be called afterward.
+    pub fn iter_all_mut(&mut self) -> impl Iterator<Item=(Idx, &mut Pos<Value>)> {
+        self.values.iter_mut()
+            .enumerate()
+            .map(|(position, pos)| (Self::index_of(position), pos))
     }
 
     /// In-Place iterator over all stored value (This excludes empty and reserved positions).
-    pub fn into_iter(self) -> impl Iterator<Item=Value> {
-        self.values.into_iter()
-            .filter(|pos| pos.is_used())
-            .map(|pos| pos.opt().unwrap())
+    /// <br>
+    /// <br>
+    /// Its length is known from [FixedIndexVec::used_spaces_len], making it an [ExactSizeIterator],
+    /// and once exhausted it keeps returning [Option::None], making it a
+    /// [FusedIterator](core::iter::FusedIterator).
+    #[allow(clippy::should_implement_trait)]
+    pub fn into_iter(self) -> IntoIter<Value> {
+        IntoIter { remaining: self.used_spaces_len(), positions: self.values.into_iter() }
     }
 
     /// In-Place iterator over all stored value (This excludes empty and reserved positions) and
-    /// their indexes..
-    pub fn into_iter_index(self) -> impl Iterator<Item=(usize, Value)> {
+    /// their indexes.
+    /// <br>
+    /// <br>
+    /// Unlike the [IntoIterator] implementation, which yields an [Option] for every position, this
+    /// only yields used positions, keeping their indexes.
+    pub fn into_iter_index(self) -> impl Iterator<Item=(Idx, Value)> {
         self.values.into_iter()
             .enumerate()
             .filter(|(_, pos)| pos.is_used())
-            .map(|(index, pos)| (index, pos.opt().unwrap()))
+            .map(|(position, pos)| (Self::index_of(position), pos.opt().unwrap()))
     }
 }
\ No newline at end of file
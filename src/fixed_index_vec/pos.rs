@@ -26,26 +26,17 @@ impl<Value> Default for Pos<Value> {
 impl<Value> Pos<Value> {
     /// Return if this position is the variant of [Pos::Empty]
     pub fn is_empty(&self) -> bool {
-        match self {
-            Empty => { true }
-            _ => { false }
-        }
+        matches!(self, Empty)
     }
 
     /// Return if this position is the variant of [Pos::Used]
     pub fn is_used(&self) -> bool {
-        match self {
-            Used(_) => { true }
-            _ => { false }
-        }
+        matches!(self, Used(_))
     }
 
     /// Return if this position is the variant of [Pos::Reserved]
     pub fn is_reserved(&self) -> bool {
-        match self {
-            Reserved => { true }
-            _ => { false }
-        }
+        matches!(self, Reserved)
     }
 
     /// Returns value as option, being [Option::Some] only if this variant was [Pos::Used]
@@ -73,4 +64,147 @@ impl<Value> Pos<Value> {
             _ => None,
         }
     }
+
+    /// Maps the contained value through the function if this variant is [Pos::Used], keeping
+    /// [Pos::Empty] and [Pos::Reserved] as they are
+    pub fn map<U, F: FnOnce(Value) -> U>(self, f: F) -> Pos<U> {
+        match self {
+            Empty => Empty,
+            Reserved => Reserved,
+            Used(value) => Used(f(value)),
+        }
+    }
+
+    /// Returns this position holding a reference to the contained value if this variant is
+    /// [Pos::Used]
+    pub fn as_ref(&self) -> Pos<&Value> {
+        match self {
+            Empty => Empty,
+            Reserved => Reserved,
+            Used(value) => Used(value),
+        }
+    }
+
+    /// Returns this position holding a mutable reference to the contained value if this variant is
+    /// [Pos::Used]
+    pub fn as_mut(&mut self) -> Pos<&mut Value> {
+        match self {
+            Empty => Empty,
+            Reserved => Reserved,
+            Used(value) => Used(value),
+        }
+    }
+
+    /// Returns the contained value if this variant is [Pos::Used], or the default value otherwise
+    pub fn unwrap_or(self, default: Value) -> Value {
+        match self {
+            Used(value) => value,
+            _ => default,
+        }
+    }
+
+    /// Returns the contained value if this variant is [Pos::Used], or the value returned by the
+    /// function otherwise
+    pub fn unwrap_or_else<F: FnOnce() -> Value>(self, f: F) -> Value {
+        match self {
+            Used(value) => value,
+            _ => f(),
+        }
+    }
+
+    /// Returns the contained value if this variant is [Pos::Used]
+    /// <br>
+    /// <br>
+    /// # Panics
+    /// Panics with the message followed by the variant that was found if this variant is
+    /// [Pos::Empty] or [Pos::Reserved]
+    #[track_caller]
+    pub fn expect_used(self, msg: &str) -> Value {
+        match self {
+            Used(value) => value,
+            Empty => panic!("{msg}: position was Empty"),
+            Reserved => panic!("{msg}: position was Reserved"),
+        }
+    }
+
+    /// Replaces this position with the new one, returning the previous position
+    /// <br>
+    /// <br>
+    /// Note that when this position belongs to a [super::FixedIndexVec], changing its variant
+    /// doesn't update which positions it knows are empty or reserved, so callers should only
+    /// replace positions with ones of the same variant, call
+    /// [super::FixedIndexVec::recompute_bookkeeping] afterward, or use methods such as
+    /// [super::FixedIndexVec::remove] and [super::FixedIndexVec::push_reserved] instead
+    pub fn replace(&mut self, new: Pos<Value>) -> Pos<Value> {
+        core::mem::replace(self, new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use crate::fixed_index_vec::pos::Pos;
+    use crate::fixed_index_vec::pos::Pos::{Empty, Reserved, Used};
+
+    #[test]
+    fn map_transforms_only_used_values() {
+        assert_eq!(Used(2).map(|value| value * 3), Used(6));
+        assert_eq!(Pos::<i32>::Empty.map(|value| value * 3), Empty);
+        assert_eq!(Pos::<i32>::Reserved.map(|value| value * 3), Reserved);
+        assert_eq!(Used(2).map(|value| value.to_string()), Used(String::from("2")));
+    }
+
+    #[test]
+    fn as_ref_and_as_mut_borrow_the_value_keeping_the_variant() {
+        assert_eq!(Used(2).as_ref(), Used(&2));
+        assert_eq!(Pos::<i32>::Empty.as_ref(), Empty);
+        assert_eq!(Pos::<i32>::Reserved.as_ref(), Reserved);
+        let mut pos = Used(2);
+        if let Used(value) = pos.as_mut() { *value = 5; }
+        assert_eq!(pos, Used(5));
+        assert_eq!(Pos::<i32>::Empty.as_mut(), Empty);
+        assert_eq!(Pos::<i32>::Reserved.as_mut(), Reserved);
+    }
+
+    #[test]
+    fn unwrap_or_defaults_only_without_a_value() {
+        assert_eq!(Used(2).unwrap_or(9), 2);
+        assert_eq!(Pos::Empty.unwrap_or(9), 9);
+        assert_eq!(Pos::Reserved.unwrap_or(9), 9);
+    }
+
+    #[test]
+    fn unwrap_or_else_calls_the_function_only_without_a_value() {
+        assert_eq!(Used(2).unwrap_or_else(|| unreachable!()), 2);
+        assert_eq!(Pos::Empty.unwrap_or_else(|| 8), 8);
+        assert_eq!(Pos::Reserved.unwrap_or_else(|| 8), 8);
+    }
+
+    #[test]
+    fn expect_used_returns_the_value() {
+        assert_eq!(Used(2).expect_used("slot 3 missing"), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "slot 3 missing: position was Empty")]
+    fn expect_used_panics_naming_the_empty_variant() {
+        let _: i32 = Pos::Empty.expect_used("slot 3 missing");
+    }
+
+    #[test]
+    #[should_panic(expected = "slot 3 missing: position was Reserved")]
+    fn expect_used_panics_naming_the_reserved_variant() {
+        let _: i32 = Pos::Reserved.expect_used("slot 3 missing");
+    }
+
+    #[test]
+    fn replace_returns_the_previous_position_for_every_variant() {
+        let mut pos: Pos<i32> = Empty;
+        assert_eq!(pos.replace(Reserved), Empty);
+        assert_eq!(pos.replace(Used(3)), Reserved);
+        assert_eq!(pos.replace(Used(4)), Used(3));
+        assert_eq!(pos.replace(Empty), Used(4));
+        assert_eq!(pos, Empty);
+    }
 }
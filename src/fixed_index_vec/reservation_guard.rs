@@ -0,0 +1,96 @@
+use crate::fixed_index_vec::FixedIndexVec;
+use crate::fixed_index_vec::vec_index::VecIndex;
+
+/// Holds a reserved index of a [FixedIndexVec], returned by [FixedIndexVec::reserve_guard].
+/// <br>
+/// <br>
+/// Once dropped, the reservation is cancelled through [FixedIndexVec::remove_reserved_pos] unless
+/// a value was stored on it through [ReservationGuard::fill].
+pub struct ReservationGuard<'values, Value, Idx: VecIndex> {
+    pub(super) fixed_index_vec: &'values mut FixedIndexVec<Value, Idx>,
+    pub(super) index: Idx,
+    pub(super) filled: bool,
+}
+
+impl<Value, Idx: VecIndex> ReservationGuard<'_, Value, Idx> {
+    /// Returns the reserved index this guard holds.
+    pub fn index(&self) -> Idx {
+        self.index
+    }
+
+    /// Stores the value on the reserved index, committing the reservation, and returns said index.
+    pub fn fill(mut self, value: Value) -> Idx {
+        //The guard borrows the FixedIndexVec for its whole life, so the index is still reserved
+        self.filled = self.fixed_index_vec.push_reserved(self.index, value).is_none();
+        self.index
+    }
+}
+
+impl<Value, Idx: VecIndex> Drop for ReservationGuard<'_, Value, Idx> {
+    fn drop(&mut self) {
+        if self.filled { return; }
+        self.fixed_index_vec.remove_reserved_pos(self.index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed_index_vec::FixedIndexVec;
+
+    #[test]
+    fn dropped_guard_cancels_its_reservation() {
+        let mut fixed_index_vec = FixedIndexVec::new();
+        fixed_index_vec.push(0);
+        fixed_index_vec.push(1);
+        {
+            let guard = fixed_index_vec.reserve_guard();
+            assert_eq!(guard.index(), 2);
+        }
+        assert_eq!(fixed_index_vec.reserved_spaces_len(), 0);
+        assert_eq!(fixed_index_vec.len(), 2);
+
+        fixed_index_vec.remove(0);
+        let guard = fixed_index_vec.reserve_guard();
+        assert_eq!(guard.index(), 0);
+        drop(guard);
+        assert_eq!(fixed_index_vec.reserved_spaces_len(), 0);
+        assert_eq!(fixed_index_vec.empty_spaces_len(), 1);
+        assert_eq!(fixed_index_vec.lowest_free_index(), 0);
+        assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn filled_guard_commits_its_reservation() {
+        let mut fixed_index_vec = FixedIndexVec::new();
+        fixed_index_vec.push(0);
+        fixed_index_vec.push(1);
+        fixed_index_vec.remove(0);
+        let guard = fixed_index_vec.reserve_guard();
+        assert_eq!(guard.fill(7), 0);
+        assert_eq!(fixed_index_vec.get(0), Some(&7));
+        assert_eq!(fixed_index_vec.reserved_spaces_len(), 0);
+        assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn cancelled_reservation_is_reused() {
+        let mut fixed_index_vec = FixedIndexVec::new();
+        fixed_index_vec.push(0);
+        let reserved = fixed_index_vec.reserve_pos();
+        fixed_index_vec.push(2);
+        assert!(fixed_index_vec.remove_reserved_pos(reserved));
+        assert!(!fixed_index_vec.remove_reserved_pos(reserved));
+        assert_eq!(fixed_index_vec.push(8), reserved);
+    }
+
+    #[test]
+    fn cancelled_trailing_reservation_is_released_with_auto_shrink() {
+        let mut fixed_index_vec = FixedIndexVec::new();
+        fixed_index_vec.set_auto_shrink(true);
+        fixed_index_vec.push(0);
+        let reserved = fixed_index_vec.extend_reserve(64);
+        reserved.into_iter().rev().for_each(|index| { fixed_index_vec.remove_reserved_pos(index); });
+        assert_eq!(fixed_index_vec.len(), 1);
+        assert!(fixed_index_vec.capacity() < 64);
+    }
+}
@@ -0,0 +1,24 @@
+/// Decides which empty position of a [super::FixedIndexVec] is reused first when pushing or
+/// reserving, set through [super::FixedIndexVec::set_reuse_policy].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReusePolicy {
+    /// Reuses the empty position with the lowest index first, keeping values as compact as
+    /// possible, this is the default policy.
+    #[default]
+    LowestIndex,
+    /// Reuses the position that was emptied most recently first, such as by removing its value or
+    /// cancelling its reservation, so that memory that was just freed, and is likely still cached,
+    /// is reused before older empty positions.
+    /// <br>
+    /// <br>
+    /// Empty positions that weren't freed while this policy was set, such as those left before
+    /// setting it or those created by [super::FixedIndexVec::reinsert] beyond the end of the Vec,
+    /// are reused afterward, starting from the lowest index.
+    /// <br>
+    /// <br>
+    /// As the reused position has to be taken out of the middle of the sorted empty positions,
+    /// pushing and reserving become O(n) in the worst case, being a binary search followed by
+    /// shifting the empty positions after it, plus skipping the freed indexes that were reused or
+    /// trimmed since.
+    MostRecent,
+}
@@ -0,0 +1,13 @@
+/// Outcome of [super::FixedIndexVec::swap_remove], telling which value was removed and which index,
+/// if any, changed as a result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapRemoveOutcome<Value, Idx = usize> {
+    /// The index held no value, so nothing was removed nor moved.
+    NotPresent,
+    /// The value was removed, but no other value had to be moved as it was the value with the
+    /// highest index.
+    RemovedNoMove(Value),
+    /// The value was removed and the value with the highest index was moved from `moved_from` into
+    /// `moved_to`, which is the index of the removed value.
+    RemovedMoved { value: Value, moved_from: Idx, moved_to: Idx },
+}
@@ -0,0 +1,1918 @@
+extern crate std;
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::fixed_index_vec::FixedIndexVec;
+use crate::fixed_index_vec::error::{CompressPlanError, DuplicateIndexError, FixedIndexError, GetManyError, InvalidPartsError, InvariantError};
+use crate::fixed_index_vec::pos::Pos;
+use crate::fixed_index_vec::reuse_policy::ReusePolicy;
+use crate::fixed_index_vec::swap_remove_outcome::SwapRemoveOutcome;
+use crate::fixed_index_vec::update_action::UpdateAction;
+use crate::fixed_index_vec::vec_index::VecIndex;
+
+#[test]
+fn compress_stats_counts_moves_and_reclaimed_slots() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(5);
+    //[0, _, 2, 3, 4, _, 6, 7]
+    let (result, stats) = fixed_index_vec.compress_stats(true);
+    assert_eq!(result.0, vec![(7, 1), (6, 5)]);
+    assert_eq!((stats.moves, stats.reclaimed_slots, stats.final_len), (2, 2, 6));
+    assert_eq!(fixed_index_vec.iter().copied().collect::<Vec<_>>(), vec![0, 7, 2, 3, 4, 6]);
+}
+
+#[test]
+fn compress_stats_without_vacancies_does_nothing() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+    let (result, stats) = fixed_index_vec.compress_stats(true);
+    assert!(result.0.is_empty());
+    assert_eq!((stats.moves, stats.reclaimed_slots, stats.final_len), (0, 0, 4));
+}
+
+#[test]
+fn compress_stats_without_saving_results_still_counts_the_moves() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(5);
+    let (result, stats) = fixed_index_vec.compress_stats(false);
+    //The moves aren't collected at all when they aren't saved
+    assert_eq!(result.0.capacity(), 0);
+    assert_eq!((stats.moves, stats.reclaimed_slots, stats.final_len), (2, 2, 6));
+    assert_eq!(fixed_index_vec.iter().copied().collect::<Vec<_>>(), vec![0, 7, 2, 3, 4, 6]);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn get_unchecked_reads_used_values() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+    fixed_index_vec.remove(1);
+    for index in [0, 2, 3] {
+        assert_eq!(unsafe { *fixed_index_vec.get_unchecked(index) }, index as u32);
+    }
+    unsafe { *fixed_index_vec.get_unchecked_mut(2) += 10; }
+    assert_eq!(fixed_index_vec.get(2), Some(&12));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "FixedIndexVec::get_unchecked on index 1 which holds no value")]
+fn get_unchecked_panics_on_debug_builds_when_the_index_holds_no_value() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+    fixed_index_vec.remove(1);
+    let _ = unsafe { fixed_index_vec.get_unchecked(1) };
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "FixedIndexVec::get_unchecked_mut on index 9 which holds no value")]
+fn get_unchecked_mut_panics_on_debug_builds_when_the_index_is_out_of_range() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+    let _ = unsafe { fixed_index_vec.get_unchecked_mut(9) };
+}
+
+#[test]
+fn reinsert_fills_an_empty_position_or_extends_the_vec() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    assert_eq!(fixed_index_vec.remove(2), Some(2));
+    assert_eq!(fixed_index_vec.reinsert(2, 9), Ok(()));
+    assert_eq!(fixed_index_vec.get(2), Some(&9));
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+
+    assert_eq!(fixed_index_vec.reinsert(8, 8), Ok(()));
+    assert_eq!(fixed_index_vec.get(8), Some(&8));
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 3);
+    assert_eq!(fixed_index_vec.push(1), 5);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn reinsert_returns_the_value_when_the_position_was_reclaimed() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec.remove(2);
+    assert_eq!(fixed_index_vec.push(20), 2);
+    assert_eq!(fixed_index_vec.reinsert(2, 7), Err(7));
+    assert_eq!(fixed_index_vec.get(2), Some(&20));
+
+    fixed_index_vec.remove(3);
+    assert_eq!(fixed_index_vec.reserve_pos(), 3);
+    assert_eq!(fixed_index_vec.reinsert(3, 7), Err(7));
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 1);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn extend_reserve_allocates_once_for_the_whole_batch() {
+    let mut fixed_index_vec = FixedIndexVec::<u32>::new();
+    let reserved = fixed_index_vec.extend_reserve(1000);
+    //Growing one position at a time would have doubled the capacity up to 1024 instead
+    assert_eq!(fixed_index_vec.capacity(), 1000);
+    assert_eq!(reserved, (0..1000).collect::<Vec<_>>());
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 1000);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn extend_reserve_reuses_empty_positions_first() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(4);
+    assert_eq!(fixed_index_vec.extend_reserve(4), vec![1, 4, 6, 7]);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+    assert_eq!(fixed_index_vec.push_reserved(7, 70), None);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn swap_compact_remove_many_removes_scattered_values_and_backfills_from_the_tail() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    let (removed, moves) = fixed_index_vec.swap_compact_remove_many([1, 3, 3, 6, 20]);
+    assert_eq!(removed, vec![1, 3, 6]);
+    assert_eq!(moves, vec![(7, 1), (5, 3)]);
+    assert_eq!(fixed_index_vec.iter().copied().collect::<Vec<_>>(), vec![0, 7, 2, 5, 4]);
+    assert_eq!(fixed_index_vec.len(), 5);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn try_push_reserved_fills_reservations_and_tells_why_it_failed() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+    fixed_index_vec.remove(1);
+    let reserved = fixed_index_vec.reserve_pos();
+    fixed_index_vec.remove(0);
+    assert_eq!(fixed_index_vec.try_push_reserved(9, 7), Err((7, FixedIndexError::OutOfBounds)));
+    assert_eq!(fixed_index_vec.try_push_reserved(2, 7), Err((7, FixedIndexError::NotReserved)));
+    assert_eq!(fixed_index_vec.try_push_reserved(0, 7), Err((7, FixedIndexError::NotReserved)));
+    assert_eq!(fixed_index_vec.try_push_reserved(reserved, 7), Ok(1));
+    assert_eq!(fixed_index_vec.get(1), Some(&7));
+    assert_eq!(fixed_index_vec.try_push_reserved(reserved, 8), Err((8, FixedIndexError::NotReserved)));
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 0);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn compress_avoiding_keeps_the_listed_indexes_in_place() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    fixed_index_vec.remove(5);
+    //[0, _, 2, _, 4, _, 6, 7], where 7 can't be moved and 3 can't be filled
+    let result = fixed_index_vec.compress_avoiding(&[7, 3], true);
+    assert_eq!(result.0, vec![(6, 1)]);
+    assert!(result.0.iter().all(|&(old, new)| ![7, 3].contains(&old) && ![7, 3].contains(&new)));
+    assert_eq!(fixed_index_vec.get(7), Some(&7));
+    assert_eq!(fixed_index_vec.get(1), Some(&6));
+    assert_eq!(fixed_index_vec.len(), 8);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 3);
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.push(10), 3);
+    assert_eq!(fixed_index_vec.push(11), 5);
+    assert_eq!(fixed_index_vec.push(12), 6);
+}
+
+#[test]
+fn compress_avoiding_nothing_compresses_like_compress() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(5);
+    let mut compressed = fixed_index_vec.clone();
+    assert_eq!(fixed_index_vec.compress_avoiding(&[], true).0, compressed.compress(true).0);
+    assert_eq!(fixed_index_vec, compressed);
+}
+
+#[test]
+fn compress_avoiding_ignores_repeated_kept_indexes() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    fixed_index_vec.remove(5);
+    let mut kept_once = fixed_index_vec.clone();
+    let result = fixed_index_vec.compress_avoiding(&[7, 3, 7, 3], true);
+    assert_eq!(result.0, kept_once.compress_avoiding(&[3, 7], true).0);
+    assert_eq!(fixed_index_vec, kept_once);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn into_iter_index_yields_owned_values_with_their_indexes() {
+    let mut fixed_index_vec: FixedIndexVec<String> = ["a", "b", "c", "d", "e"].into_iter().map(String::from).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    fixed_index_vec.reserve_pos();
+    let positions: Vec<Option<String>> = IntoIterator::into_iter(fixed_index_vec.clone()).collect();
+    assert_eq!(positions, vec![Some(String::from("a")), None, Some(String::from("c")), None, Some(String::from("e"))]);
+    let pairs: Vec<(usize, String)> = fixed_index_vec.into_iter_index().collect();
+    assert_eq!(pairs, vec![(0, String::from("a")), (2, String::from("c")), (4, String::from("e"))]);
+}
+
+#[test]
+fn get_or_insert_returns_the_existing_value() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+    assert_eq!(*fixed_index_vec.get_or_insert(1, 9), 1);
+    assert_eq!(fixed_index_vec.len(), 3);
+}
+
+#[test]
+fn get_or_insert_installs_the_default_on_reserved_empty_or_missing_positions() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+    let reserved = fixed_index_vec.reserve_pos();
+    assert_eq!(*fixed_index_vec.get_or_insert(reserved, 9), 9);
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 0);
+
+    *fixed_index_vec.get_or_insert(6, 5) += 1;
+    assert_eq!(fixed_index_vec.get(6), Some(&6));
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 2);
+
+    assert_eq!(*fixed_index_vec.get_or_insert(4, 4), 4);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 1);
+    assert_eq!(fixed_index_vec.push(5), 5);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn first_and_last_skip_valueless_positions() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+    fixed_index_vec.remove(0);
+    fixed_index_vec.reserve_pos();
+    fixed_index_vec.reserve_pos();
+    //[R, 1, 2, R]
+    assert_eq!(fixed_index_vec.first(), Some((1, &1)));
+    assert_eq!(fixed_index_vec.last(), Some((2, &2)));
+    assert_eq!(FixedIndexVec::<u8>::new().first(), None);
+    assert_eq!(FixedIndexVec::<u8>::new().last(), None);
+}
+
+#[test]
+fn retain_into_moves_rejected_values_and_reports_where_they_landed() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    fixed_index_vec.remove(2);
+    fixed_index_vec.remove(5);
+    let mut dest: FixedIndexVec<u32> = (100..102).collect();
+    dest.remove(0);
+    let moved = fixed_index_vec.retain_into(&mut dest, |_, value| value % 2 == 0);
+    assert_eq!(moved, vec![(1, 0), (3, 2), (7, 3)]);
+    assert_eq!(fixed_index_vec.iter_index().map(|(index, value)| (index, *value)).collect::<Vec<_>>(), vec![(0, 0), (4, 4), (6, 6)]);
+    assert_eq!(fixed_index_vec.len(), 7);
+    assert_eq!(dest.iter().copied().collect::<Vec<_>>(), vec![1, 101, 3, 7]);
+    assert_consistent(&fixed_index_vec);
+    assert_consistent(&dest);
+    for index in [1, 2, 3, 5] {
+        assert_eq!(fixed_index_vec.push(9), index);
+    }
+}
+
+#[test]
+fn push_reuses_the_lowest_free_index_first() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(4);
+    fixed_index_vec.remove(2);
+    for index in [1, 2, 4] {
+        assert_eq!(fixed_index_vec.lowest_free_index(), index);
+        assert_eq!(fixed_index_vec.push(0), index);
+    }
+    assert_eq!(fixed_index_vec.lowest_free_index(), 6);
+    assert_eq!(fixed_index_vec.push(0), 6);
+}
+
+#[test]
+fn get_cloned_clones_only_present_values() {
+    let mut fixed_index_vec: FixedIndexVec<String> = ["a", "b"].into_iter().map(String::from).collect();
+    fixed_index_vec.remove(0);
+    let reserved = fixed_index_vec.reserve_pos();
+    assert_eq!(fixed_index_vec.get_cloned(1), Some(String::from("b")));
+    assert_eq!(fixed_index_vec.get_cloned(reserved), None);
+    assert_eq!(fixed_index_vec.get_cloned(5), None);
+}
+
+#[test]
+fn swap_with_exchanges_contents_and_reports_previous_lengths() {
+    let mut front: FixedIndexVec<u32> = (0..5).collect();
+    front.remove(2);
+    let mut back: FixedIndexVec<u32> = (10..12).collect();
+    back.reserve_pos();
+    assert_eq!(front.swap_with(&mut back), (5, 3));
+    assert_eq!(front.iter_index().collect::<Vec<_>>(), vec![(0, &10), (1, &11)]);
+    assert_eq!(front.reserved_spaces_len(), 1);
+    assert_eq!(back.iter_index().collect::<Vec<_>>(), vec![(0, &0), (1, &1), (3, &3), (4, &4)]);
+    assert_eq!(back.push(2), 2);
+    assert_consistent(&front);
+    assert_consistent(&back);
+}
+
+#[test]
+fn compress_preview_matches_compress_without_mutating() {
+    for seed in 0..200u64 {
+        let mut fixed_index_vec: FixedIndexVec<u64> = (0..20).collect();
+        let mut state = seed * 2654435761 + 1;
+        for _ in 0..12 {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let index = ((state >> 33) % 20) as usize;
+            fixed_index_vec.remove(index);
+            if index % 3 == 1 { fixed_index_vec.reserve_pos(); }
+        }
+        let before = fixed_index_vec.clone();
+        let preview = fixed_index_vec.compress_preview();
+        assert_eq!(fixed_index_vec, before);
+        let result = fixed_index_vec.compress(true);
+        assert_eq!(preview, result.0);
+        assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+        for (index, value) in before.iter_index() {
+            let new_index = preview.iter().find(|(old, _)| *old == index).map_or(index, |&(_, new)| new);
+            assert_eq!(fixed_index_vec.get(new_index), Some(value));
+        }
+    }
+}
+
+#[test]
+fn lowest_index_policy_reuses_lowest_vacancy_first() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(4);
+    assert_eq!(fixed_index_vec.reuse_policy(), ReusePolicy::LowestIndex);
+    assert_eq!(fixed_index_vec.push(10), 1);
+    assert_eq!(fixed_index_vec.reserve_pos(), 4);
+}
+
+#[test]
+fn most_recent_policy_reuses_last_freed_position_first() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.set_reuse_policy(ReusePolicy::MostRecent);
+    fixed_index_vec.remove(4);
+    fixed_index_vec.remove(1);
+    assert_eq!(fixed_index_vec.push(10), 1);
+    assert_eq!(fixed_index_vec.push(11), 4);
+
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(4);
+    let reserved = fixed_index_vec.reserve_pos();
+    assert_eq!(reserved, 4);
+    assert!(fixed_index_vec.remove_reserved_pos(reserved));
+    assert_eq!(fixed_index_vec.push(12), 4);
+    assert_eq!(fixed_index_vec.push(13), 1);
+    assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+}
+
+#[test]
+fn most_recent_policy_skips_indexes_no_longer_empty() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    fixed_index_vec.set_reuse_policy(ReusePolicy::MostRecent);
+    fixed_index_vec.remove(2);
+    fixed_index_vec.remove(5);
+    fixed_index_vec.remove(7);
+    //Index 7 was trimmed from the right end and index 5 is taken back by reinserting
+    assert_eq!(fixed_index_vec.reinsert(5, 50), Ok(()));
+    assert_eq!(fixed_index_vec.push(20), 2);
+    assert_eq!(fixed_index_vec.push(70), 7);
+}
+
+#[test]
+fn most_recent_policy_reuses_untracked_vacancies_from_lowest() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    fixed_index_vec.set_reuse_policy(ReusePolicy::MostRecent);
+    fixed_index_vec.remove(6);
+    assert_eq!(fixed_index_vec.push(60), 6);
+    assert_eq!(fixed_index_vec.push(10), 1);
+    assert_eq!(fixed_index_vec.push(30), 3);
+    assert_eq!(fixed_index_vec.push(80), 8);
+}
+
+#[test]
+fn most_recent_policy_follows_bulk_removals() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..10).collect();
+    fixed_index_vec.set_reuse_policy(ReusePolicy::MostRecent);
+    fixed_index_vec.remove(8);
+    fixed_index_vec.retain(|index, _| index != 2 && index != 5);
+    assert_eq!(fixed_index_vec.push(50), 5);
+    assert_eq!(fixed_index_vec.push(20), 2);
+    assert_eq!(fixed_index_vec.push(80), 8);
+}
+
+#[test]
+fn most_recent_policy_stays_consistent_over_many_cycles() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..64).collect();
+    fixed_index_vec.set_reuse_policy(ReusePolicy::MostRecent);
+    for round in 0..200u32 {
+        let index = (round as usize * 37) % 63;
+        if fixed_index_vec.remove(index).is_some() {
+            assert_eq!(fixed_index_vec.push(round), index);
+        }
+        if round % 50 == 0 {
+            fixed_index_vec.compress(false);
+        }
+    }
+    assert!(fixed_index_vec.recently_freed.len() <= 2 * fixed_index_vec.empty_spaces_len() + 16);
+    assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+}
+
+#[test]
+fn values_at_gathers_one_option_per_index_in_order() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec.remove(1);
+    let reserved = fixed_index_vec.reserve_pos();
+    let indexes = [4, reserved, 0, 9, 4];
+    let gathered: Vec<Option<&u32>> = fixed_index_vec.values_at(&indexes).collect();
+    assert_eq!(gathered, vec![Some(&4), None, Some(&0), None, Some(&4)]);
+    assert_eq!(fixed_index_vec.values_at(&[]).count(), 0);
+}
+
+#[test]
+fn remove_if_removes_only_when_the_predicate_holds() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+    assert_eq!(fixed_index_vec.remove_if(1, |value| *value > 5), None);
+    assert_eq!(fixed_index_vec.get(1), Some(&1));
+    assert_eq!(fixed_index_vec.remove_if(1, |value| *value == 1), Some(1));
+    assert!(!fixed_index_vec.contains_index(1));
+    assert_eq!(fixed_index_vec.remove_if(1, |_| unreachable!()), None);
+    assert_eq!(fixed_index_vec.remove_if(9, |_| unreachable!()), None);
+    assert_eq!(fixed_index_vec.remove_if(3, |_| true), Some(3));
+    assert_eq!(fixed_index_vec.len(), 3);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn is_packed_follows_removals_reservations_and_refills() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+    assert!(fixed_index_vec.is_packed());
+    assert!(fixed_index_vec.iter_index().map(|(index, _)| index).eq(0..fixed_index_vec.len()));
+    fixed_index_vec.remove(1);
+    assert!(!fixed_index_vec.is_packed());
+    let reserved = fixed_index_vec.reserve_pos();
+    assert!(!fixed_index_vec.is_packed());
+    fixed_index_vec.push_reserved(reserved, 1);
+    assert!(fixed_index_vec.is_packed());
+    fixed_index_vec.remove(3);
+    assert!(fixed_index_vec.is_packed());
+    assert!(FixedIndexVec::<u32>::new().is_packed());
+}
+
+#[test]
+fn u32_indexes_are_returned_and_accepted_everywhere() {
+    let mut fixed_index_vec: FixedIndexVec<&str, u32> = FixedIndexVec::with_index_type();
+    let indexes: Vec<u32> = ["a", "b", "c"].into_iter().map(|value| fixed_index_vec.push(value)).collect();
+    assert_eq!(indexes, vec![0, 1, 2]);
+    fixed_index_vec.remove(1);
+    assert_eq!(fixed_index_vec.get(2), Some(&"c"));
+    assert_eq!(fixed_index_vec.push("d"), 1);
+    assert_eq!(fixed_index_vec.reserve_pos(), 3);
+    fixed_index_vec.remove(0);
+    assert_eq!(fixed_index_vec.compress(true).0, vec![(3u32, 0u32)]);
+    assert_eq!(fixed_index_vec.iter_index().collect::<Vec<_>>(), vec![(1, &"d"), (2, &"c")]);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn push_panics_once_the_index_type_runs_out_of_indexes() {
+    let fixed_index_vec: FixedIndexVec<u8, u8> = (0..=255u8).collect();
+    assert_eq!(fixed_index_vec.len(), 256);
+    let mut full = fixed_index_vec.clone();
+    expect_panic(|| { full.push(1); });
+    let mut with_vacancy = fixed_index_vec;
+    with_vacancy.remove(7);
+    assert_eq!(with_vacancy.push(1), 7);
+}
+
+#[test]
+fn usize_stays_the_inferred_index_type() {
+    let fixed_index_vec = FixedIndexVec::new();
+    let _: &FixedIndexVec<u8> = &fixed_index_vec;
+    let from_array = FixedIndexVec::from([1, 2]);
+    let _: &FixedIndexVec<i32, usize> = &from_array;
+    assert_eq!(from_array.len(), 2);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn get_checked_detects_indexes_whose_position_was_reused() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+    let generation = fixed_index_vec.generation(1);
+    assert_eq!(fixed_index_vec.get_checked(1, generation), Some(&1));
+    fixed_index_vec.remove(1);
+    assert_eq!(fixed_index_vec.get_checked(1, generation), None);
+    assert_eq!(fixed_index_vec.push(9), 1);
+    assert_eq!(fixed_index_vec.get_checked(1, generation), None);
+    assert_eq!(fixed_index_vec.get_checked(1, fixed_index_vec.generation(1)), Some(&9));
+
+    let trimmed_generation = fixed_index_vec.generation(2);
+    fixed_index_vec.remove(2);
+    assert_eq!(fixed_index_vec.push(5), 2);
+    assert_eq!(fixed_index_vec.get_checked(2, trimmed_generation), None);
+}
+
+#[test]
+fn get_checked_accepts_current_generations() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+    fixed_index_vec.remove(0);
+    assert_eq!(fixed_index_vec.get_checked(2, fixed_index_vec.generation(2)), Some(&2));
+    assert_eq!(fixed_index_vec.get_checked(0, fixed_index_vec.generation(0)), None);
+}
+
+#[test]
+fn extend_from_slice_matches_extend() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    fixed_index_vec.reserve_pos();
+    let mut extended = fixed_index_vec.clone();
+    let slice = [10, 11, 12, 13];
+    fixed_index_vec.extend_from_slice(&slice);
+    extended.extend(slice.iter().copied());
+    assert_eq!(fixed_index_vec, extended);
+    assert_eq!(fixed_index_vec.as_positions(), extended.as_positions());
+    assert_eq!(fixed_index_vec.get(3), Some(&10));
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn as_positions_maps_indexes_directly_to_positions() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    let reserved = fixed_index_vec.reserve_pos();
+    let positions = fixed_index_vec.as_positions();
+    assert_eq!(positions.len(), fixed_index_vec.len());
+    assert_eq!(positions, &[Pos::Used(0), Pos::Reserved, Pos::Used(2), Pos::Empty, Pos::Used(4)]);
+    for (index, position) in positions.iter().enumerate() {
+        assert_eq!(position, &fixed_index_vec[index]);
+        assert_eq!(position.is_used(), fixed_index_vec.contains_index(index));
+    }
+    assert!(positions[reserved].is_reserved());
+}
+
+#[test]
+fn vacancies_in_counts_empty_positions_within_the_range() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..10).collect();
+    for index in [1, 2, 5, 8] {
+        fixed_index_vec.remove(index);
+    }
+    fixed_index_vec.reserve_pos();
+    //[0, R, _, 3, 4, _, 6, 7, _, 9]
+    assert_eq!(fixed_index_vec.vacancies_in(0..10), 3);
+    assert_eq!(fixed_index_vec.vacancies_in(2..5), 1);
+    assert_eq!(fixed_index_vec.vacancies_in(2..6), 2);
+    assert_eq!(fixed_index_vec.vacancies_in(3..5), 0);
+    assert_eq!(fixed_index_vec.vacancies_in(8..100), 1);
+    assert_eq!(fixed_index_vec.vacancies_in(5..5), 0);
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = 6..2;
+    assert_eq!(fixed_index_vec.vacancies_in(reversed), 0);
+}
+
+#[test]
+fn compress_bounded_stops_after_max_moves() {
+    for max_moves in 0..6 {
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..10).collect();
+        for index in [1, 2, 4, 8] {
+            fixed_index_vec.remove(index);
+        }
+        //[0, _, _, 3, _, 5, 6, 7, _, 9]
+        let mut fully_compressed = fixed_index_vec.clone();
+        let full_moves = fully_compressed.compress(true).0;
+        let result = fixed_index_vec.compress_bounded(max_moves, true);
+        assert_eq!(result.0, full_moves[..max_moves.min(full_moves.len())]);
+        assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+        assert_eq!(fixed_index_vec.used_spaces_len(), 6);
+        let mut values = fixed_index_vec.iter().copied().collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 3, 5, 6, 7, 9]);
+    }
+}
+
+#[test]
+fn compress_bounded_keeps_remaining_vacancies_reusable() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..10).collect();
+    for index in [1, 2, 4, 8] {
+        fixed_index_vec.remove(index);
+    }
+    let result = fixed_index_vec.compress_bounded(1, true);
+    //Only the value on index 9 was moved, into index 1, leaving 8 and 9 trimmed
+    assert_eq!(result.0, vec![(9, 1)]);
+    assert_eq!(fixed_index_vec.len(), 8);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 2);
+    assert_eq!(fixed_index_vec.push(20), 2);
+    assert_eq!(fixed_index_vec.push(40), 4);
+    assert_eq!(fixed_index_vec.push(80), 8);
+
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..10).collect();
+    for index in [0, 1, 7] {
+        fixed_index_vec.remove(index);
+    }
+    //The value on index 9 is moved into index 0, leaving the empty positions on indexes 1 and 7
+    fixed_index_vec.compress_bounded(1, false);
+    assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+    assert_eq!(fixed_index_vec.len(), 9);
+    assert_eq!(fixed_index_vec.push(10), 1);
+    assert_eq!(fixed_index_vec.push(70), 7);
+}
+
+#[test]
+fn get2_mut_indexed_returns_both_values_with_their_indexes() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+    fixed_index_vec.remove(1);
+    let ((first_index, first), (second_index, second)) = fixed_index_vec.get2_mut_indexed(3, 0).unwrap();
+    assert_eq!((first_index, *first, second_index, *second), (3, 3, 0, 0));
+    core::mem::swap(first, second);
+    assert_eq!(fixed_index_vec.get(0), Some(&3));
+    assert_eq!(fixed_index_vec.get(3), Some(&0));
+}
+
+#[test]
+fn get2_mut_indexed_rejects_repeated_or_valueless_indexes() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+    fixed_index_vec.remove(1);
+    assert!(fixed_index_vec.get2_mut_indexed(2, 2).is_none());
+    assert!(fixed_index_vec.get2_mut_indexed(1, 2).is_none());
+    assert!(fixed_index_vec.get2_mut_indexed(2, 1).is_none());
+    assert!(fixed_index_vec.get2_mut_indexed(2, 9).is_none());
+}
+
+#[test]
+fn auto_shrink_releases_memory_after_a_large_trim() {
+    let mut shrinking: FixedIndexVec<u32> = (0..100).collect();
+    let mut growing = shrinking.clone();
+    shrinking.set_auto_shrink(true);
+    for index in (2..100).rev() {
+        shrinking.remove(index);
+        growing.remove(index);
+    }
+    assert_eq!(shrinking.len(), 2);
+    assert!(shrinking.capacity() < 10);
+    assert!(growing.capacity() >= 100);
+}
+
+#[test]
+fn auto_shrink_keeps_memory_while_the_length_is_large_enough() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = FixedIndexVec::with_capacity_and_vacancies(64, 0);
+    fixed_index_vec.extend(0..64);
+    fixed_index_vec.set_auto_shrink(true);
+    for index in (20..64).rev() {
+        fixed_index_vec.remove(index);
+    }
+    //Shrinking only starts once the capacity exceeds four times the length
+    for index in (16..20).rev() {
+        fixed_index_vec.remove(index);
+    }
+    assert_eq!(fixed_index_vec.capacity(), 64);
+    fixed_index_vec.remove(15);
+    assert!(fixed_index_vec.capacity() < 64);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn try_push_and_try_reserve_pos_signal_index_exhaustion() {
+    let mut fixed_index_vec: FixedIndexVec<u8, u8> = FixedIndexVec::with_index_type();
+    for value in 0..=255u8 {
+        assert_eq!(fixed_index_vec.try_push(value), Ok(value));
+    }
+    assert_eq!(fixed_index_vec.try_push(1), Err(1));
+    assert_eq!(fixed_index_vec.try_reserve_pos(), None);
+    assert_eq!(fixed_index_vec.len(), 256);
+
+    fixed_index_vec.remove(7);
+    assert_eq!(fixed_index_vec.try_reserve_pos(), Some(7));
+    assert_eq!(fixed_index_vec.try_push(1), Err(1));
+    fixed_index_vec.remove(255);
+    assert_eq!(fixed_index_vec.try_push(1), Ok(255));
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn indices_rev_yields_used_indexes_in_descending_order() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(4);
+    assert_eq!(fixed_index_vec.reserve_pos(), 1);
+    assert_eq!(fixed_index_vec.indices_rev().collect::<Vec<_>>(), vec![5, 3, 2, 0]);
+    assert_eq!(FixedIndexVec::<u32>::new().indices_rev().count(), 0);
+}
+
+#[test]
+fn compress_reserved_tail_places_values_then_reservations() {
+    let mut fixed_index_vec = FixedIndexVec::new();
+    for value in 0..4 {
+        fixed_index_vec.push(value);
+    }
+    let first_reserved = fixed_index_vec.reserve_pos();
+    fixed_index_vec.push(10);
+    let second_reserved = fixed_index_vec.reserve_pos();
+    fixed_index_vec.push(20);
+    fixed_index_vec.remove(0);
+    fixed_index_vec.remove(2);
+    //[_, 1, _, 3, R, 10, R, 20]
+    assert_eq!((first_reserved, second_reserved), (4, 6));
+    let result = fixed_index_vec.compress_reserved_tail(true);
+    assert_eq!(result.0, vec![(5, 0), (7, 2), (6, 5)]);
+    let positions = fixed_index_vec.as_positions();
+    assert_eq!(positions.len(), 6);
+    assert!(positions[..4].iter().all(Pos::is_used));
+    assert!(positions[4..].iter().all(Pos::is_reserved));
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+    assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+}
+
+#[test]
+fn compress_reserved_tail_reports_moved_reservations_as_fillable() {
+    let mut fixed_index_vec = FixedIndexVec::new();
+    let low_reserved = fixed_index_vec.reserve_pos();
+    for value in 1..6 {
+        fixed_index_vec.push(value);
+    }
+    let high_reserved = fixed_index_vec.reserve_pos();
+    fixed_index_vec.push(7);
+    fixed_index_vec.remove(2);
+    //[R, 1, _, 3, 4, 5, R, 7]
+    let result = fixed_index_vec.compress_reserved_tail(true);
+    let translate = |index| result.0.iter().find(|(old_index, _)| *old_index == index).map_or(index, |&(_, new_index)| new_index);
+    let (low_reserved, high_reserved) = (translate(low_reserved), translate(high_reserved));
+    assert_eq!(fixed_index_vec.push_reserved(low_reserved, 100), None);
+    assert_eq!(fixed_index_vec.push_reserved(high_reserved, 600), None);
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 0);
+    let mut values = fixed_index_vec.iter().copied().collect::<Vec<_>>();
+    values.sort_unstable();
+    assert_eq!(values, vec![1, 3, 4, 5, 7, 100, 600]);
+}
+
+/// Runs the function expecting it to panic, as a test would if it unwound through it.
+fn expect_panic(f: impl FnOnce()) {
+    assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).is_err());
+}
+
+/// Checks the vec is consistent and its iterators yield as many values as they report.
+fn assert_consistent<Value, Idx: VecIndex>(fixed_index_vec: &FixedIndexVec<Value, Idx>) {
+    assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+    assert_eq!(fixed_index_vec.iter().len(), fixed_index_vec.iter().count());
+}
+
+#[test]
+fn from_sorted_pairs_leaves_gaps_as_vacancies() {
+    let fixed_index_vec = FixedIndexVec::<_, usize>::from_sorted_pairs([(1, 'a'), (2, 'b'), (5, 'c')]);
+    assert_eq!(fixed_index_vec.len(), 6);
+    assert_eq!(fixed_index_vec.iter_index().collect::<Vec<_>>(), vec![(1, &'a'), (2, &'b'), (5, &'c')]);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 3);
+    assert_eq!(fixed_index_vec.lowest_free_index(), 0);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "FixedIndexVec::from_sorted_pairs received index 2 after index 3")]
+fn from_sorted_pairs_panics_on_debug_builds_if_unsorted() {
+    let _ = FixedIndexVec::<_, usize>::from_sorted_pairs([(3, 'a'), (2, 'b')]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "FixedIndexVec::from_sorted_pairs received index 3 after index 3")]
+fn from_sorted_pairs_panics_on_debug_builds_if_an_index_repeats() {
+    let _ = FixedIndexVec::<_, usize>::from_sorted_pairs([(3, 'a'), (3, 'b')]);
+}
+
+#[test]
+fn to_dense_returns_aligned_indexes_and_values() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).map(|value| value * 10).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(4);
+    fixed_index_vec.reserve_pos();
+    let (indexes, values) = fixed_index_vec.to_dense();
+    assert_eq!(indexes, vec![0, 2, 3, 5]);
+    assert_eq!(values, vec![0, 20, 30, 50]);
+    for (index, value) in indexes.iter().zip(&values) {
+        assert_eq!(fixed_index_vec.get(*index), Some(value));
+    }
+}
+
+#[test]
+fn retain_matches_removing_one_by_one() {
+    let mut retained = FixedIndexVec::new();
+    for value in 0..200 {
+        retained.push(value);
+    }
+    for index in (0..200).step_by(7) {
+        retained.remove(index);
+    }
+    let mut removed_one_by_one = retained.clone();
+    retained.retain(|_, value| value % 3 != 0);
+    for index in 0..200 {
+        if removed_one_by_one.get(index).is_some_and(|value| value % 3 == 0) {
+            removed_one_by_one.remove(index);
+        }
+    }
+    assert_eq!(retained, removed_one_by_one);
+    assert!(retained.vacancies.iter().zip(retained.vacancies.iter().skip(1)).all(|(previous, next)| previous < next));
+    for _ in 0..100 {
+        assert_eq!(retained.push(0), removed_one_by_one.push(0));
+    }
+}
+
+#[test]
+fn retain_registers_removed_positions_when_the_predicate_panics() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    expect_panic(|| fixed_index_vec.retain(|index, _| {
+        if index == 5 { panic!("predicate failed"); }
+        index % 2 == 0
+    }));
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.iter().copied().collect::<Vec<_>>(), vec![0, 2, 4, 5, 6, 7]);
+    fixed_index_vec.compress(false);
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.len(), 6);
+}
+
+#[test]
+fn retain_registers_removed_positions_when_a_drop_panics() {
+    struct PanicOnDrop(u32);
+    impl Drop for PanicOnDrop {
+        fn drop(&mut self) {
+            if self.0 == 3 && !std::thread::panicking() { panic!("drop failed"); }
+        }
+    }
+    let mut fixed_index_vec: FixedIndexVec<PanicOnDrop> = (0..6).map(PanicOnDrop).collect();
+    expect_panic(|| fixed_index_vec.retain(|_, value| value.0 % 2 == 0));
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.iter().map(|value| value.0).collect::<Vec<_>>(), vec![0, 2, 4, 5]);
+}
+
+#[test]
+fn retain_into_registers_moved_positions_when_the_predicate_panics() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    let mut dest = FixedIndexVec::new();
+    expect_panic(|| {
+        fixed_index_vec.retain_into(&mut dest, |index, _| {
+            if index == 4 { panic!("predicate failed"); }
+            index % 2 == 0
+        });
+    });
+    assert_consistent(&fixed_index_vec);
+    assert_consistent(&dest);
+    assert_eq!(fixed_index_vec.iter().copied().collect::<Vec<_>>(), vec![0, 2, 4, 5, 6, 7]);
+    assert_eq!(dest.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+}
+
+#[test]
+fn remove_all_eq_registers_removed_positions_when_eq_panics() {
+    #[derive(Debug)]
+    struct PanicOnEq(u32);
+    impl PartialEq for PanicOnEq {
+        fn eq(&self, other: &Self) -> bool {
+            if self.0 == 4 { panic!("eq failed"); }
+            self.0 == other.0
+        }
+    }
+    let mut fixed_index_vec = FixedIndexVec::new();
+    for value in [1, 7, 7, 4, 7] {
+        fixed_index_vec.push(PanicOnEq(value));
+    }
+    expect_panic(|| { fixed_index_vec.remove_all_eq(&PanicOnEq(7)); });
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.iter().map(|value| value.0).collect::<Vec<_>>(), vec![1, 4, 7]);
+}
+
+#[test]
+fn drain_filter_registers_drained_positions_when_the_predicate_panics() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    let mut drained = Vec::new();
+    expect_panic(|| {
+        for pair in fixed_index_vec.drain_filter(|index, _| {
+            if index == 6 { panic!("predicate failed"); }
+            index % 2 == 1
+        }) {
+            drained.push(pair);
+        }
+    });
+    assert_eq!(drained, vec![(1, 1), (3, 3), (5, 5)]);
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.push(10), 1);
+}
+
+#[test]
+fn swap_compact_remove_many_registers_removed_positions_when_the_indexes_panic() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    let indexes = [2, 5, 9].into_iter().map(|index| if index == 9 { panic!("index failed") } else { index });
+    expect_panic(|| { fixed_index_vec.swap_compact_remove_many(indexes); });
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.used_spaces_len(), 6);
+    assert_eq!(fixed_index_vec.push(20), 2);
+}
+
+#[test]
+fn position_of_max_and_min_by_key_prefer_the_lowest_index_on_ties() {
+    let mut fixed_index_vec: FixedIndexVec<i32> = [5, 9, 1, 9, 1, 3].into_iter().collect();
+    fixed_index_vec.remove(0);
+    assert_eq!(fixed_index_vec.position_of_max_by_key(|value| *value), Some(1));
+    assert_eq!(fixed_index_vec.position_of_min_by_key(|value| *value), Some(2));
+    assert_eq!(fixed_index_vec.position_of_max_by_key(|value| -*value), Some(2));
+    fixed_index_vec.remove(1);
+    assert_eq!(fixed_index_vec.position_of_max_by_key(|value| *value), Some(3));
+    assert_eq!(FixedIndexVec::<i32>::new().position_of_max_by_key(|value| *value), None);
+    assert_eq!(FixedIndexVec::<i32>::new().position_of_min_by_key(|value| *value), None);
+}
+
+#[test]
+fn swap_remove_reports_each_outcome() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec.remove(2);
+    assert_eq!(fixed_index_vec.swap_remove(2), SwapRemoveOutcome::NotPresent);
+    assert_eq!(fixed_index_vec.swap_remove(9), SwapRemoveOutcome::NotPresent);
+    assert_eq!(fixed_index_vec.swap_remove(4), SwapRemoveOutcome::RemovedNoMove(4));
+    assert_eq!(fixed_index_vec.len(), 4);
+    assert_eq!(fixed_index_vec.swap_remove(0), SwapRemoveOutcome::RemovedMoved { value: 0, moved_from: 3, moved_to: 0 });
+    assert_eq!(fixed_index_vec.get(0), Some(&3));
+    assert_eq!(fixed_index_vec.len(), 2);
+    assert_eq!(fixed_index_vec.lowest_free_index(), 2);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn compress_moves_highest_positions_into_lowest_vacancies_in_order() {
+    let mut fixed_index_vec = FixedIndexVec::new();
+    for value in 0..8 {
+        fixed_index_vec.push(value);
+    }
+    let reserved = fixed_index_vec.reserve_pos();
+    fixed_index_vec.push(9);
+    for index in [0, 2, 3, 6] {
+        fixed_index_vec.remove(index);
+    }
+    //[_, 1, _, _, 4, 5, _, 7, R, 9]
+    assert_eq!(reserved, 8);
+    assert_eq!(fixed_index_vec.compress(true).0, vec![(9, 0), (8, 2), (7, 3)]);
+    assert_eq!(fixed_index_vec.len(), 6);
+    assert!(fixed_index_vec.as_positions()[2].is_reserved());
+    assert_eq!(fixed_index_vec.iter().copied().collect::<Vec<_>>(), vec![9, 1, 7, 4, 5]);
+}
+
+#[test]
+fn range_yields_values_within_the_clamped_window() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(3);
+    fixed_index_vec.remove(1);
+    fixed_index_vec.reserve_pos();
+    assert_eq!(fixed_index_vec.range(1..5).collect::<Vec<_>>(), vec![(2, &2), (4, &4)]);
+    assert_eq!(fixed_index_vec.range(2..100).collect::<Vec<_>>(), vec![(2, &2), (4, &4), (5, &5)]);
+    assert_eq!(fixed_index_vec.range(50..100).count(), 0);
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = 4..2;
+    assert_eq!(fixed_index_vec.range(reversed).count(), 0);
+}
+
+#[test]
+fn compress_and_cap_shrinks_capacity_after_a_past_peak() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..1000).collect();
+    for index in 0..990 {
+        fixed_index_vec.remove(index);
+    }
+    assert!(fixed_index_vec.capacity() >= 1000);
+    let result = fixed_index_vec.compress_and_cap(64, true);
+    assert_eq!(result.0.len(), 10);
+    assert_eq!(fixed_index_vec.len(), 10);
+    assert!((10..=64).contains(&fixed_index_vec.capacity()));
+    fixed_index_vec.compress_and_cap(0, false);
+    assert!(fixed_index_vec.capacity() >= 10);
+    assert_eq!(fixed_index_vec.iter().copied().collect::<Vec<_>>(), (990..1000).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn occupancy_bitset_sets_one_bit_per_used_position() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..130).collect();
+    for index in (0..130).step_by(3) {
+        fixed_index_vec.remove(index);
+    }
+    fixed_index_vec.reserve_pos();
+    let bitset = fixed_index_vec.occupancy_bitset();
+    assert_eq!(bitset.len(), 3);
+    for index in 0..bitset.len() * 64 {
+        let bit = (bitset[index / 64] >> (index % 64)) & 1 == 1;
+        assert_eq!(bit, fixed_index_vec.contains_index(index), "bit {index}");
+    }
+    assert!(FixedIndexVec::<u32>::new().occupancy_bitset().is_empty());
+}
+
+#[test]
+fn with_capacity_and_vacancies_avoids_reallocating_within_both_capacities() {
+    let mut fixed_index_vec = FixedIndexVec::with_capacity_and_vacancies(100, 50);
+    let capacity = fixed_index_vec.capacity();
+    let vacancy_capacity = fixed_index_vec.vacancies.capacity();
+    assert!(capacity >= 100);
+    assert!(vacancy_capacity >= 50);
+    for value in 0..100 {
+        fixed_index_vec.push(value);
+    }
+    for index in 0..50 {
+        fixed_index_vec.remove(index * 2);
+    }
+    assert_eq!(fixed_index_vec.capacity(), capacity);
+    assert_eq!(fixed_index_vec.vacancies.capacity(), vacancy_capacity);
+}
+
+#[test]
+fn index_difference_and_intersection_compare_occupancy_only() {
+    let numbers: FixedIndexVec<i32> = FixedIndexVec::from_sorted_pairs([(0, 0), (2, 2), (4, 4), (9, 9)]);
+    let mut names: FixedIndexVec<&str> = FixedIndexVec::from_sorted_pairs([(1, "a"), (2, "b"), (4, "c"), (5, "d")]);
+    names.remove(5);
+    names.reserve_pos();
+    assert_eq!(numbers.index_difference(&names).collect::<Vec<_>>(), vec![0, 9]);
+    assert_eq!(numbers.index_intersection(&names).collect::<Vec<_>>(), vec![2, 4]);
+    assert_eq!(names.index_difference(&numbers).collect::<Vec<_>>(), vec![1]);
+    let disjoint: FixedIndexVec<u8> = FixedIndexVec::from_sorted_pairs([(5, 1)]);
+    assert_eq!(numbers.index_intersection(&disjoint).count(), 0);
+    assert_eq!(numbers.index_difference(&disjoint).collect::<Vec<_>>(), vec![0, 2, 4, 9]);
+}
+
+#[test]
+fn retain_and_maybe_compress_skips_compressing_below_the_threshold() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..10).collect();
+    assert!(fixed_index_vec.retain_and_maybe_compress(|_, value| *value != 3, 0.5).is_none());
+    assert_eq!(fixed_index_vec.len(), 10);
+    assert!(!fixed_index_vec.contains_index(3));
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn retain_and_maybe_compress_compresses_above_the_threshold() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..10).collect();
+    let before = fixed_index_vec.clone();
+    let result = fixed_index_vec.retain_and_maybe_compress(|_, value| *value % 2 == 1, 0.2).unwrap();
+    assert_eq!(fixed_index_vec.len(), 5);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+    for (index, value) in before.iter_index().filter(|(_, value)| *value % 2 == 1) {
+        let new_index = result.0.iter().find(|(old, _)| *old == index).map_or(index, |&(_, new)| new);
+        assert_eq!(fixed_index_vec.get(new_index), Some(value));
+    }
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn get_many_mut_returns_every_value_or_the_offending_index() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec.remove(1);
+    let [fourth, first] = fixed_index_vec.get_many_mut([4, 0]).unwrap();
+    *fourth += 10;
+    *first += 20;
+    assert_eq!(fixed_index_vec.get(4), Some(&14));
+    assert_eq!(fixed_index_vec.get(0), Some(&20));
+    assert_eq!(fixed_index_vec.get_many_mut([0, 2, 0]).err(), Some(GetManyError::Duplicate(0)));
+    assert_eq!(fixed_index_vec.get_many_mut([0, 1]).err(), Some(GetManyError::NotPresent(1)));
+    assert_eq!(fixed_index_vec.get_many_mut([9, 1]).err(), Some(GetManyError::OutOfBounds(9)));
+    assert!(fixed_index_vec.get_many_mut([]).is_ok());
+}
+
+#[test]
+fn normalized_vecs_compare_equal_regardless_of_fragmentation() {
+    let mut fragmented: FixedIndexVec<u32> = (0..6).collect();
+    fragmented.remove(0);
+    fragmented.remove(3);
+    let mut other: FixedIndexVec<u32> = [1, 9, 2, 4, 5].into_iter().collect();
+    other.remove(1);
+    assert_ne!(fragmented, other);
+    assert_eq!(fragmented.normalized(), other.normalized());
+}
+
+#[test]
+fn normalize_keeps_the_order_of_values_and_reservations() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(0);
+    fixed_index_vec.remove(3);
+    fixed_index_vec.remove(4);
+    assert_eq!(fixed_index_vec.reserve_pos(), 0);
+    //[R, 1, 2, _, _, 5]
+    let result = fixed_index_vec.normalize();
+    assert_eq!(result.0, vec![(5, 3)]);
+    assert_eq!(fixed_index_vec.as_positions(), &[Pos::Reserved, Pos::Used(1), Pos::Used(2), Pos::Used(5)]);
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.push(6), 4);
+}
+
+#[test]
+fn has_no_slots_tells_apart_reservations_from_an_empty_vec() {
+    let mut fixed_index_vec = FixedIndexVec::new();
+    assert!(fixed_index_vec.has_no_slots());
+    let reserved = fixed_index_vec.reserve_pos();
+    assert!(!fixed_index_vec.has_no_slots());
+    assert_eq!(fixed_index_vec.used_spaces_len(), 0);
+    fixed_index_vec.push_reserved(reserved, 1);
+    fixed_index_vec.push(2);
+    fixed_index_vec.remove(1);
+    assert!(!fixed_index_vec.has_no_slots());
+    fixed_index_vec.remove(0);
+    assert!(fixed_index_vec.has_no_slots());
+}
+
+#[test]
+fn compress_with_plan_applies_a_valid_plan() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(0);
+    fixed_index_vec.remove(2);
+    //[_, 1, _, 3, 4, 5]
+    assert_eq!(fixed_index_vec.compress_with_plan(&[(4, 0), (5, 2)]), Ok(()));
+    assert_eq!(fixed_index_vec.iter_index().collect::<Vec<_>>(), vec![(0, &4), (1, &1), (2, &5), (3, &3)]);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.push(7), 4);
+}
+
+#[test]
+fn compress_with_plan_rejects_the_first_invalid_move_without_changes() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(0);
+    fixed_index_vec.remove(2);
+    let original = fixed_index_vec.clone();
+    assert_eq!(fixed_index_vec.compress_with_plan(&[(4, 0), (4, 2)]), Err(CompressPlanError::SourceNotUsed { move_index: 1, source: 4 }));
+    assert_eq!(fixed_index_vec.compress_with_plan(&[(4, 0), (5, 1)]), Err(CompressPlanError::TargetNotEmpty { move_index: 1, target: 1 }));
+    assert_eq!(fixed_index_vec.compress_with_plan(&[(9, 0)]), Err(CompressPlanError::SourceNotUsed { move_index: 0, source: 9 }));
+    assert_eq!(fixed_index_vec.compress_with_plan(&[(5, 9)]), Err(CompressPlanError::TargetNotEmpty { move_index: 0, target: 9 }));
+    assert_eq!(fixed_index_vec, original);
+    assert_eq!(fixed_index_vec.as_positions(), original.as_positions());
+}
+
+#[test]
+fn for_each_indexed_mut_records_indexes_while_mutating() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec.remove(2);
+    fixed_index_vec.reserve_pos();
+    let mut visited = Vec::new();
+    fixed_index_vec.for_each_indexed_mut(|index, value| {
+        visited.push(index);
+        *value *= 10;
+    });
+    assert_eq!(visited, vec![0, 1, 3, 4]);
+    assert_eq!(fixed_index_vec.iter().copied().collect::<Vec<_>>(), vec![0, 10, 30, 40]);
+}
+
+#[test]
+fn trim_reserved_tail_removes_trailing_reservations_and_empty_positions_before_them() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+    let kept_reservation = fixed_index_vec.reserve_pos();
+    fixed_index_vec.push(3);
+    let trimmed_reservations = fixed_index_vec.extend_reserve(3);
+    fixed_index_vec.remove(4);
+    //[0, 1, 2, R, _, R, R, R]
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 4);
+    assert_eq!(fixed_index_vec.trim_reserved_tail(), 3);
+    assert_eq!(fixed_index_vec.len(), 4);
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 1);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.push_reserved(trimmed_reservations[1], 9), Some(9));
+    assert_eq!(fixed_index_vec.push_reserved(kept_reservation, 9), None);
+    assert_eq!(fixed_index_vec.trim_reserved_tail(), 0);
+    assert_eq!(fixed_index_vec.push(4), 4);
+}
+
+#[test]
+fn update_keeps_or_removes_the_value_as_the_function_decides() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+    assert_eq!(fixed_index_vec.update(1, |value| { *value += 5; UpdateAction::Keep }), None);
+    assert_eq!(fixed_index_vec.get(1), Some(&6));
+    assert_eq!(fixed_index_vec.update(1, |value| { *value += 5; UpdateAction::Remove }), Some(11));
+    assert!(!fixed_index_vec.contains_index(1));
+    assert_eq!(fixed_index_vec.update(1, |_| unreachable!()), None);
+    assert_eq!(fixed_index_vec.update(9, |_| unreachable!()), None);
+    assert_eq!(fixed_index_vec.update(2, |_| UpdateAction::Remove), Some(2));
+    assert_eq!(fixed_index_vec.len(), 1);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn trailing_reserved_counts_reservations_up_to_the_last_other_position() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+    fixed_index_vec.extend_reserve(2);
+    fixed_index_vec.push(9);
+    fixed_index_vec.reserve_pos();
+    //[0, 1, 2, R, R, 9, R]
+    assert_eq!(fixed_index_vec.trailing_reserved(), 1);
+    assert_eq!(fixed_index_vec.trailing_empty(), 0);
+    fixed_index_vec.remove(5);
+    //[0, 1, 2, R, R, _, R]
+    assert_eq!(fixed_index_vec.trailing_reserved(), 1);
+    fixed_index_vec.trim_reserved_tail();
+    assert_eq!(fixed_index_vec.trailing_reserved(), 2);
+    assert_eq!(fixed_index_vec.trailing_empty(), 0);
+}
+
+#[test]
+fn trailing_empty_counts_positions_emptied_without_trimming() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec[4] = Pos::Empty;
+    fixed_index_vec[3] = Pos::Empty;
+    fixed_index_vec[1] = Pos::Empty;
+    assert_eq!(fixed_index_vec.trailing_empty(), 2);
+    assert_eq!(fixed_index_vec.trailing_reserved(), 0);
+    fixed_index_vec.recompute_bookkeeping();
+    assert_eq!(fixed_index_vec.trailing_empty(), 0);
+    assert_eq!(FixedIndexVec::<u32>::new().trailing_empty(), 0);
+}
+
+#[test]
+fn extend_positions_restores_used_reserved_and_empty_positions() {
+    let mut fixed_index_vec = FixedIndexVec::new();
+    fixed_index_vec.push(0);
+    fixed_index_vec.remove(0);
+    fixed_index_vec.push(1);
+    fixed_index_vec.extend_positions([Pos::Used(2), Pos::Reserved, Pos::Empty, Pos::Used(3)]);
+    //[1, 2, R, _, 3]
+    assert_eq!(fixed_index_vec.len(), 5);
+    assert_eq!(fixed_index_vec.used_spaces_len(), 3);
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 1);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 1);
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.push_reserved(2, 9), None);
+    assert_eq!(fixed_index_vec.push(4), 3);
+}
+
+#[test]
+fn extend_positions_trims_trailing_empty_positions() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..2).collect();
+    fixed_index_vec.extend_positions([Pos::Used(2), Pos::Empty, Pos::Empty]);
+    assert_eq!(fixed_index_vec.len(), 3);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn extend_positions_trims_trailing_empty_positions_when_the_iterator_panics() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..2).collect();
+    let positions = [Pos::Used(2), Pos::Reserved, Pos::Empty, Pos::Empty].into_iter()
+        .chain(core::iter::from_fn(|| panic!("iterator panicked")));
+    expect_panic(|| fixed_index_vec.extend_positions(positions));
+    //[0, 1, 2, R]
+    assert_eq!(fixed_index_vec.len(), 4);
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 1);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn try_compress_applies_every_move_once_all_are_accepted() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(0);
+    fixed_index_vec.remove(2);
+    let preview = fixed_index_vec.compress_preview();
+    let mut validated = Vec::new();
+    let result = fixed_index_vec.try_compress(|old, new| { validated.push((old, new)); true }).unwrap();
+    assert_eq!(result.0, validated);
+    assert_eq!(result.0, preview);
+    assert_eq!(fixed_index_vec.len(), 4);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn try_compress_leaves_the_vec_untouched_when_a_move_is_rejected() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(0);
+    fixed_index_vec.remove(2);
+    let original = fixed_index_vec.clone();
+    assert!(fixed_index_vec.try_compress(|old, _| old != 4).is_err());
+    assert_eq!(fixed_index_vec, original);
+    assert_eq!(fixed_index_vec.as_positions(), original.as_positions());
+}
+
+#[test]
+fn visit_used_and_visit_used_mut_call_the_function_per_used_position() {
+    let mut fixed_index_vec: FixedIndexVec<usize> = (0..6).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(4);
+    fixed_index_vec.reserve_pos();
+    let mut visited = Vec::new();
+    fixed_index_vec.visit_used(|index, value| visited.push((index, *value)));
+    assert_eq!(visited, vec![(0, 0), (2, 2), (3, 3), (5, 5)]);
+    let mut calls = 0;
+    fixed_index_vec.visit_used_mut(|index, value| { calls += 1; *value = index * 2; });
+    assert_eq!(calls, 4);
+    assert_eq!(fixed_index_vec.get(5), Some(&10));
+}
+
+#[test]
+fn singleton_holds_its_only_value_at_index_zero() {
+    let (fixed_index_vec, index) = FixedIndexVec::singleton("a");
+    assert_eq!(index, 0);
+    assert_eq!(fixed_index_vec.get(0), Some(&"a"));
+    assert_eq!(fixed_index_vec.len(), 1);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn replace_with_transforms_used_values_only() {
+    let mut fixed_index_vec = FixedIndexVec::new();
+    fixed_index_vec.push(String::from("a"));
+    fixed_index_vec.push(String::from("b"));
+    fixed_index_vec.remove(1);
+    let reserved = fixed_index_vec.reserve_pos();
+    assert!(fixed_index_vec.replace_with(0, |value| value + "!"));
+    assert_eq!(fixed_index_vec.get(0).map(String::as_str), Some("a!"));
+    assert!(!fixed_index_vec.replace_with(reserved, |_| unreachable!()));
+    assert!(!fixed_index_vec.replace_with(7, |_| unreachable!()));
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 1);
+}
+
+#[test]
+fn replace_with_leaves_the_index_empty_when_the_function_panics() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+    let generation = fixed_index_vec.generation(1);
+    expect_panic(|| { fixed_index_vec.replace_with(1, |_| panic!("transform failed")); });
+    assert_consistent(&fixed_index_vec);
+    assert!(!fixed_index_vec.contains_index(1));
+    assert_eq!(fixed_index_vec.used_spaces_len(), 3);
+    assert_eq!(fixed_index_vec.get_checked(1, generation), None);
+    assert_eq!(fixed_index_vec.push(10), 1);
+
+    expect_panic(|| { fixed_index_vec.replace_with(3, |_| panic!("transform failed")); });
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.len(), 3);
+}
+
+#[test]
+fn compress_suffix_leaves_indexes_below_from_untouched() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..10).collect();
+    for index in [1, 3, 5, 6, 8] {
+        fixed_index_vec.remove(index);
+    }
+    //[0, _, 2, _, 4, _, _, 7, _, 9]
+    let result = fixed_index_vec.compress_suffix(4, true);
+    assert_eq!(result.0, vec![(9, 5), (7, 6)]);
+    assert!(result.0.iter().all(|&(old, new)| old >= 4 && new >= 4));
+    assert_eq!(fixed_index_vec.len(), 7);
+    assert_eq!(fixed_index_vec.get(0), Some(&0));
+    assert_eq!(fixed_index_vec.get(2), Some(&2));
+    assert!(!fixed_index_vec.contains_index(1) && !fixed_index_vec.contains_index(3));
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 2);
+    assert_consistent(&fixed_index_vec);
+    for index in [1, 3, 7] {
+        assert_eq!(fixed_index_vec.push(10), index);
+    }
+}
+
+#[test]
+fn compress_suffix_trims_a_fully_empty_suffix() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    fixed_index_vec.remove(4);
+    assert!(fixed_index_vec.compress_suffix(3, true).0.is_empty());
+    assert_eq!(fixed_index_vec.len(), 3);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 1);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn try_from_pairs_builds_from_unique_indexes_in_any_order() {
+    let fixed_index_vec = FixedIndexVec::<_, usize>::try_from_pairs([(4, 'a'), (1, 'b'), (2, 'c')]).unwrap();
+    assert_eq!(fixed_index_vec.len(), 5);
+    assert_eq!(fixed_index_vec.iter_index().collect::<Vec<_>>(), vec![(1, &'b'), (2, &'c'), (4, &'a')]);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 2);
+    assert_eq!(fixed_index_vec.lowest_free_index(), 0);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn try_from_pairs_rejects_the_first_repeated_index() {
+    let result = FixedIndexVec::<_, usize>::try_from_pairs([(4, 'a'), (1, 'b'), (1, 'c'), (4, 'd')]);
+    assert_eq!(result.err(), Some(DuplicateIndexError { index: 1 }));
+}
+
+#[test]
+fn reserve_for_index_allows_filling_that_index_without_reallocating() {
+    let mut fixed_index_vec = FixedIndexVec::new();
+    fixed_index_vec.push(0);
+    fixed_index_vec.reserve_for_index(500);
+    let capacity = fixed_index_vec.capacity();
+    assert!(capacity >= 501);
+    fixed_index_vec.reinsert(500, 5).unwrap();
+    assert_eq!(fixed_index_vec.capacity(), capacity);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 499);
+    fixed_index_vec.reserve_for_index(3);
+    assert_eq!(fixed_index_vec.capacity(), capacity);
+}
+
+#[test]
+fn from_parts_round_trips_into_parts() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    let reserved = fixed_index_vec.reserve_pos();
+    let (values, vacancies, reserved_spaces) = fixed_index_vec.clone().into_parts();
+    let mut rebuilt = FixedIndexVec::from_parts(values, vacancies, reserved_spaces).unwrap();
+    assert_consistent(&rebuilt);
+    assert_eq!(rebuilt.reserved_spaces_len(), 1);
+    assert_eq!(rebuilt.iter().collect::<Vec<_>>(), fixed_index_vec.iter().collect::<Vec<_>>());
+    assert_eq!(rebuilt.push_reserved(reserved, 9), None);
+    assert_consistent(&rebuilt);
+}
+
+#[test]
+fn from_parts_rejects_inconsistent_parts() {
+    use Pos::{Empty, Reserved, Used};
+    type Vec32 = FixedIndexVec<u32>;
+    assert_eq!(Vec32::from_parts(vec![Used(0), Used(1)], VecDeque::from([1]), 0).unwrap_err(), InvalidPartsError::VacancyNotEmpty(1));
+    assert_eq!(Vec32::from_parts(vec![Used(0)], VecDeque::from([3]), 0).unwrap_err(), InvalidPartsError::VacancyNotEmpty(3));
+    assert_eq!(Vec32::from_parts(vec![Empty, Empty, Used(2)], VecDeque::from([1, 0]), 0).unwrap_err(), InvalidPartsError::UnsortedVacancies);
+    assert_eq!(Vec32::from_parts(vec![Empty, Empty, Used(2)], VecDeque::from([0]), 0).unwrap_err(), InvalidPartsError::UnregisteredEmptyPositions { vacancies: 1, empty_positions: 2 });
+    assert_eq!(Vec32::from_parts(vec![Reserved, Used(1)], VecDeque::new(), 0).unwrap_err(), InvalidPartsError::ReservedCountMismatch { reserved_spaces: 0, reserved_positions: 1 });
+}
+
+#[test]
+fn from_parts_rejects_a_trailing_empty_position() {
+    use Pos::{Empty, Used};
+    let result = FixedIndexVec::<u32>::from_parts(vec![Used(1), Empty], VecDeque::from([1]), 0);
+    assert_eq!(result.unwrap_err(), InvalidPartsError::TrailingEmpty);
+    assert_eq!(FixedIndexVec::<u32>::from_parts(vec![Empty], VecDeque::from([0]), 0).unwrap_err(), InvalidPartsError::TrailingEmpty);
+    let fixed_index_vec = FixedIndexVec::<u32>::from_parts(vec![Empty, Used(1)], VecDeque::from([0]), 0).unwrap();
+    assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+}
+
+#[test]
+fn pos_or_empty_treats_out_of_range_indexes_as_empty() {
+    let mut fixed_index_vec = FixedIndexVec::new();
+    for value in ["a", "b", "c"] {
+        fixed_index_vec.push(String::from(value));
+    }
+    fixed_index_vec.remove(1);
+    assert_eq!(fixed_index_vec.pos_or_empty(0), &Pos::Used(String::from("a")));
+    assert_eq!(fixed_index_vec.pos_or_empty(1), &Pos::Empty);
+    assert_eq!(fixed_index_vec.pos_or_empty(100), &Pos::Empty);
+}
+
+#[test]
+fn swap_compact_remove_many_reports_the_net_remapping() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..12).collect();
+    fixed_index_vec.remove(3);
+    let before = fixed_index_vec.clone();
+    let (removed, remap) = fixed_index_vec.swap_compact_remove_many([10, 0, 5, 10, 3, 7]);
+    assert_eq!(removed, vec![10, 0, 5, 7]);
+    assert_eq!(fixed_index_vec.len(), 7);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+    assert_consistent(&fixed_index_vec);
+    //Every old index is reported at most once
+    let mut old_indexes: Vec<usize> = remap.iter().map(|&(old, _)| old).collect();
+    old_indexes.sort_unstable();
+    old_indexes.dedup();
+    assert_eq!(old_indexes.len(), remap.len());
+    for (index, value) in before.iter_index() {
+        if removed.contains(value) {
+            continue;
+        }
+        let new_index = remap.iter().find(|&&(old, _)| old == index).map_or(index, |&(_, new)| new);
+        assert_eq!(fixed_index_vec.get(new_index), Some(value));
+    }
+    //Replaying the removals and then the reported moves one by one gives the same layout
+    let mut replay = before.clone();
+    for index in [10, 0, 5, 3, 7] {
+        replay.remove(index);
+    }
+    for &(old, new) in &remap {
+        let value = replay.remove(old).unwrap();
+        replay.reinsert(new, value).unwrap();
+    }
+    assert_eq!(replay.iter_index().collect::<Vec<_>>(), fixed_index_vec.iter_index().collect::<Vec<_>>());
+}
+
+#[test]
+fn retain_frees_indexes_to_be_reused_in_ascending_order() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..10).collect();
+    fixed_index_vec.retain(|index, _| ![5, 2, 8].contains(&index));
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.push(100), 2);
+    assert_eq!(fixed_index_vec.push(101), 5);
+    assert_eq!(fixed_index_vec.push(102), 8);
+}
+
+#[test]
+fn push_within_capacity_never_grows() {
+    let mut fixed_index_vec = FixedIndexVec::with_capacity_and_vacancies(2, 2);
+    let capacity = fixed_index_vec.capacity();
+    //Spare capacity
+    for value in 0..capacity {
+        assert_eq!(fixed_index_vec.push_within_capacity(value), Ok(value));
+    }
+    //At capacity
+    assert_eq!(fixed_index_vec.push_within_capacity(99), Err(99));
+    //Vacancy reuse
+    fixed_index_vec.remove(0);
+    assert_eq!(fixed_index_vec.push_within_capacity(7), Ok(0));
+    assert_eq!(fixed_index_vec.capacity(), capacity);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn fold_indexed_matches_a_manual_index_weighted_sum() {
+    let mut fixed_index_vec: FixedIndexVec<usize> = (1..7).collect();
+    fixed_index_vec.remove(2);
+    let sum = fixed_index_vec.fold_indexed(0, |acc, index, value| acc + index * value);
+    let mut manual = 0;
+    for index in 0..fixed_index_vec.len() {
+        if let Some(value) = fixed_index_vec.get(index) {
+            manual += index * value;
+        }
+    }
+    assert_eq!(sum, manual);
+    assert_eq!(sum, 2 + 3 * 4 + 4 * 5 + 5 * 6);
+}
+
+#[test]
+fn compress_translator_maps_old_indexes_to_new_ones() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    let translate = fixed_index_vec.compress_translator();
+    //Moved
+    assert_eq!(translate(7), 1);
+    assert_eq!(translate(6), 3);
+    //Unmoved
+    assert_eq!(translate(0), 0);
+    assert_eq!(translate(5), 5);
+    //Never present
+    assert_eq!(translate(100), 100);
+    assert_eq!(fixed_index_vec.get(translate(7)), Some(&7));
+    assert_eq!(fixed_index_vec.get(translate(6)), Some(&6));
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn max_index_counts_trailing_reservations() {
+    let mut fixed_index_vec = FixedIndexVec::new();
+    assert_eq!(fixed_index_vec.max_index(), None);
+    fixed_index_vec.push(1);
+    assert_eq!(fixed_index_vec.max_index(), Some(0));
+    fixed_index_vec.push(2);
+    fixed_index_vec.reserve_pos();
+    assert_eq!(fixed_index_vec.max_index(), Some(2));
+}
+
+#[test]
+fn clone_subset_keeps_only_the_requested_used_indexes() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).map(|value| value * 10).collect();
+    fixed_index_vec.remove(4);
+    //Repeated, no longer used and out of range indexes are skipped
+    let subset = fixed_index_vec.clone_subset([3, 0, 3, 4, 9]);
+    assert_eq!(subset.len(), 4);
+    assert_eq!(subset.get(0), Some(&0));
+    assert_eq!(subset.get(3), Some(&30));
+    assert_eq!(subset.used_spaces_len(), 2);
+    assert_eq!(subset.empty_spaces_len(), 2);
+    assert_consistent(&subset);
+}
+
+#[test]
+fn verify_invariants_accepts_states_reached_through_the_api() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    let reserved = fixed_index_vec.reserve_pos();
+    fixed_index_vec.remove(2);
+    assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+    fixed_index_vec.remove_reserved_pos(reserved);
+    assert_eq!(fixed_index_vec.verify_invariants(), Ok(()));
+}
+
+#[test]
+fn verify_invariants_catches_positions_edited_behind_its_back() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec[2] = Pos::Empty;
+    assert_eq!(fixed_index_vec.verify_invariants(),
+               Err(InvariantError::Inconsistent(InvalidPartsError::UnregisteredEmptyPositions { vacancies: 0, empty_positions: 1 })));
+
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec[1] = Pos::Reserved;
+    assert_eq!(fixed_index_vec.verify_invariants(),
+               Err(InvariantError::Inconsistent(InvalidPartsError::ReservedCountMismatch { reserved_spaces: 0, reserved_positions: 1 })));
+
+    //A registered but trailing empty position can't be reached through the API nor from_parts
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..2).collect();
+    fixed_index_vec.values.push(Pos::Empty);
+    fixed_index_vec.vacancies.push_back(2);
+    assert_eq!(fixed_index_vec.verify_invariants(), Err(InvariantError::Inconsistent(InvalidPartsError::TrailingEmpty)));
+    //from_parts rejects the same parts with the same error
+    let (values, vacancies, reserved_spaces) = fixed_index_vec.into_parts();
+    assert_eq!(FixedIndexVec::from_parts(values, vacancies, reserved_spaces).unwrap_err(), InvalidPartsError::TrailingEmpty);
+}
+
+#[test]
+fn drain_used_indices_returns_the_emptied_indexes_keeping_reservations() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    let reserved = fixed_index_vec.reserve_pos();
+    fixed_index_vec.push(6);
+    fixed_index_vec.remove(1);
+    //[0, _, 2, 3, 4, R, 6]
+    assert_eq!(fixed_index_vec.drain_used_indices(), vec![0, 2, 3, 4, 6]);
+    assert_eq!(fixed_index_vec.len(), 6);
+    assert_eq!(fixed_index_vec.used_spaces_len(), 0);
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.push_reserved(reserved, 9), None);
+    assert_eq!(fixed_index_vec.push(1), 0);
+}
+
+#[test]
+fn ensure_reserved_reserves_empty_and_missing_positions_but_not_used_ones() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+    fixed_index_vec.remove(1);
+    //Empty
+    assert_eq!(fixed_index_vec.ensure_reserved(1), Ok(()));
+    //Already reserved
+    assert_eq!(fixed_index_vec.ensure_reserved(1), Ok(()));
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 1);
+    //Beyond the end
+    assert_eq!(fixed_index_vec.ensure_reserved(6), Ok(()));
+    assert_eq!(fixed_index_vec.len(), 7);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 3);
+    //Used
+    assert_eq!(fixed_index_vec.ensure_reserved(0), Err(()));
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.push_reserved(6, 60), None);
+    assert_eq!(fixed_index_vec.push_reserved(1, 10), None);
+}
+
+#[test]
+fn iter_index_clones_resume_independently() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec.remove(3);
+    let mut iter = fixed_index_vec.iter_index();
+    iter.next();
+    let mut second_pass = iter.clone();
+    assert_eq!(iter.collect::<Vec<_>>(), vec![(1, &1), (2, &2), (4, &4)]);
+    assert_eq!(second_pass.next(), Some((1, &1)));
+    assert_eq!(second_pass.collect::<Vec<_>>(), vec![(2, &2), (4, &4)]);
+}
+
+#[test]
+fn iter_ordered_yields_values_by_ascending_index() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(4);
+    assert_eq!(fixed_index_vec.reserve_pos(), 1);
+    assert_eq!(fixed_index_vec.push(40), 4);
+    //[0, R, 2, 3, 40, 5]
+    let ordered: Vec<u32> = fixed_index_vec.iter_ordered().copied().collect();
+    assert_eq!(ordered, vec![0, 2, 3, 40, 5]);
+    assert_eq!(fixed_index_vec.iter().copied().collect::<Vec<_>>(), ordered);
+}
+
+#[test]
+fn extend_indexed_returns_where_each_value_landed() {
+    let mut fixed_index_vec: FixedIndexVec<char> = "wxyz".chars().collect();
+    fixed_index_vec.remove(2);
+    fixed_index_vec.remove(0);
+    let indexes = fixed_index_vec.extend_indexed(['a', 'b', 'c']);
+    assert_eq!(indexes, vec![0, 2, 4]);
+    for (index, value) in indexes.into_iter().zip(['a', 'b', 'c']) {
+        assert_eq!(fixed_index_vec.get(index), Some(&value));
+    }
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn compress_locality_keeps_the_hot_tail_in_order() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..10).collect();
+    assert_eq!(fixed_index_vec.reserve_pos(), 10);
+    fixed_index_vec.push(11);
+    for index in [1, 2, 4] {
+        fixed_index_vec.remove(index);
+    }
+    let mut plain_fixed_index_vec = fixed_index_vec.clone();
+    let plain = plain_fixed_index_vec.compress(true).0;
+    let local = fixed_index_vec.compress_locality(true).0;
+    assert_eq!(local, vec![(9, 1), (10, 2), (11, 4)]);
+    //Both compact fully, moving the same values
+    assert_eq!(fixed_index_vec.len(), plain_fixed_index_vec.len());
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+    assert!(fixed_index_vec.as_positions()[2].is_reserved());
+    assert_consistent(&fixed_index_vec);
+    let mut local_sources: Vec<usize> = local.iter().map(|&(old, _)| old).collect();
+    let mut plain_sources: Vec<usize> = plain.iter().map(|&(old, _)| old).collect();
+    local_sources.sort_unstable();
+    plain_sources.sort_unstable();
+    assert_eq!(local_sources, plain_sources);
+    //Only the locality aware compression keeps the moved values in their relative order
+    let keeps_order = |moves: &[(usize, usize)]| moves.windows(2).all(|pair| (pair[0].0 < pair[1].0) == (pair[0].1 < pair[1].1));
+    assert!(keeps_order(&local));
+    assert!(!keeps_order(&plain));
+}
+
+#[test]
+fn remove_all_eq_removes_every_duplicate_in_place() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = [1, 7, 2, 7, 3, 7].into_iter().collect();
+    assert_eq!(fixed_index_vec.remove_all_eq(&7), vec![1, 3, 5]);
+    assert_eq!(fixed_index_vec.len(), 5);
+    assert_eq!(fixed_index_vec.iter_index().collect::<Vec<_>>(), vec![(0, &1), (2, &2), (4, &3)]);
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.remove_all_eq(&9), Vec::<usize>::new());
+}
+
+#[test]
+fn split_at_renumbers_the_high_half_from_zero() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    for index in [2, 4, 6] {
+        fixed_index_vec.remove(index);
+    }
+    assert_eq!(fixed_index_vec.reserve_pos(), 2);
+    //[0, 1, R, 3 | _, 5, _, 7]
+    let (high, remapping) = fixed_index_vec.split_at(4);
+    assert_eq!(remapping, vec![(5, 1), (7, 3)]);
+    assert_eq!(fixed_index_vec.len(), 4);
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 1);
+    assert_eq!(fixed_index_vec.get(3), Some(&3));
+    assert_eq!(high.len(), 4);
+    assert_eq!(high.get(1), Some(&5));
+    assert_eq!(high.get(3), Some(&7));
+    assert_eq!(high.empty_spaces_len(), 2);
+    assert_consistent(&fixed_index_vec);
+    assert_consistent(&high);
+}
+
+#[test]
+fn split_at_beyond_the_end_or_at_zero_remaps_nothing() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.reserve_pos();
+    let (high, remapping) = fixed_index_vec.split_at(10);
+    assert!(high.has_no_slots() && remapping.is_empty());
+    assert_eq!(fixed_index_vec.len(), 4);
+    let (high, remapping) = fixed_index_vec.split_at(0);
+    assert!(fixed_index_vec.has_no_slots());
+    //Every position keeps its index, so there is nothing to remap
+    assert!(remapping.is_empty());
+    assert_eq!(high.iter_index().collect::<Vec<_>>(), vec![(0, &0), (2, &2), (3, &3)]);
+    assert_eq!(high.reserved_spaces_len(), 1);
+    assert_consistent(&high);
+}
+
+#[test]
+fn reset_empties_the_vec_keeping_its_capacity() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..50).collect();
+    let capacity = fixed_index_vec.capacity();
+    fixed_index_vec.reset();
+    assert!(fixed_index_vec.has_no_slots());
+    assert_eq!(fixed_index_vec.capacity(), capacity);
+    fixed_index_vec.extend(0..50);
+    assert_eq!(fixed_index_vec.capacity(), capacity);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn reset_advances_the_generation_of_every_index() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..50).collect();
+    let generation = fixed_index_vec.generation(3);
+    fixed_index_vec.reset();
+    fixed_index_vec.extend(0..50);
+    assert_eq!(fixed_index_vec.generation(3), generation + 1);
+    assert_eq!(fixed_index_vec.get_checked(3, generation), None);
+    assert_eq!(fixed_index_vec.get_checked(3, generation + 1), Some(&3));
+}
+
+#[test]
+fn get_mut_compacting_moves_a_value_into_the_gap_right_before_it() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    //[0, _, 2, _, 4]
+    let (index, value) = fixed_index_vec.get_mut_compacting(2).unwrap();
+    assert_eq!((index, *value), (1, 2));
+    *value = 20;
+    assert_eq!(fixed_index_vec.get(1), Some(&20));
+    assert!(!fixed_index_vec.contains_index(2));
+    assert_consistent(&fixed_index_vec);
+    //Moving the last value trims the vec
+    let (index, _) = fixed_index_vec.get_mut_compacting(4).unwrap();
+    assert_eq!(index, 3);
+    assert_eq!(fixed_index_vec.len(), 4);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn get_mut_compacting_keeps_values_without_a_gap_before_them() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..4).collect();
+    fixed_index_vec.remove(2);
+    assert_eq!(fixed_index_vec.get_mut_compacting(0).map(|(index, value)| (index, *value)), Some((0, 0)));
+    assert_eq!(fixed_index_vec.get_mut_compacting(1).map(|(index, value)| (index, *value)), Some((1, 1)));
+    assert!(fixed_index_vec.get_mut_compacting(2).is_none());
+    assert!(fixed_index_vec.get_mut_compacting(9).is_none());
+    assert_eq!(fixed_index_vec.iter_index().collect::<Vec<_>>(), vec![(0, &0), (1, &1), (3, &3)]);
+}
+
+#[test]
+fn iter_all_mut_edits_positions_that_recompute_bookkeeping_registers() {
+    let mut fixed_index_vec: FixedIndexVec<usize> = (0..5).collect();
+    fixed_index_vec.remove(1);
+    assert_eq!(fixed_index_vec.reserve_pos(), 1);
+    fixed_index_vec.remove(3);
+    //[0, R, 2, _, 4]
+    for (index, pos) in fixed_index_vec.iter_all_mut() {
+        if pos.is_reserved() {
+            *pos = Pos::Used(index * 100);
+        } else if index == 4 {
+            *pos = Pos::Empty;
+        } else if index == 0 {
+            *pos = Pos::Reserved;
+        }
+    }
+    //[R, 100, 2, _, _]
+    fixed_index_vec.recompute_bookkeeping();
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.len(), 3);
+    assert_eq!(fixed_index_vec.get(1), Some(&100));
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 1);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 0);
+}
+
+#[test]
+fn recompute_bookkeeping_repairs_positions_edited_through_index_mut() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..6).collect();
+    fixed_index_vec[2] = Pos::Empty;
+    fixed_index_vec[4] = Pos::Reserved;
+    fixed_index_vec[5] = Pos::Empty;
+    assert!(fixed_index_vec.verify_invariants().is_err());
+    fixed_index_vec.recompute_bookkeeping();
+    assert_consistent(&fixed_index_vec);
+    assert_eq!(fixed_index_vec.len(), 5);
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 1);
+    assert_eq!(fixed_index_vec.reserved_spaces_len(), 1);
+    assert_eq!(fixed_index_vec.push(9), 2);
+    assert_eq!(fixed_index_vec.push(10), 5);
+    assert_eq!(fixed_index_vec.push_reserved(4, 4), None);
+}
+
+#[test]
+fn move_only_values_are_pushed_removed_and_compressed() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    //Neither Clone nor Default, so every operation must move the values around
+    struct MoveOnly(Box<dyn FnOnce() -> usize>);
+    let calls = Rc::new(Cell::new(0));
+    let mut fixed_index_vec = FixedIndexVec::new();
+    for value in 0..6 {
+        let calls = calls.clone();
+        fixed_index_vec.push(MoveOnly(Box::new(move || { calls.set(calls.get() + 1); value })));
+    }
+    fixed_index_vec.remove(1);
+    let removed = fixed_index_vec.remove(2).unwrap();
+    assert_eq!((removed.0)(), 2);
+    assert_eq!(fixed_index_vec.compress(true).0, vec![(5, 1), (4, 2)]);
+    fixed_index_vec.clean_right();
+    assert_consistent(&fixed_index_vec);
+    let values: Vec<usize> = fixed_index_vec.into_iter().map(|value| (value.0)()).collect();
+    assert_eq!(values, vec![0, 5, 4, 3]);
+    assert_eq!(calls.get(), 5);
+}
+
+#[test]
+fn filter_present_keeps_only_used_candidates_in_their_order() {
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..5).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    assert_eq!(fixed_index_vec.reserve_pos(), 1);
+    //[0, R, 2, _, 4]
+    assert_eq!(fixed_index_vec.filter_present([4, 1, 3, 0, 99, 2]).collect::<Vec<_>>(), vec![4, 0, 2]);
+}
+
+#[test]
+fn from_options_turns_nones_into_empty_positions() {
+    let fixed_index_vec = FixedIndexVec::from_options([Some('a'), None, Some('c'), None]);
+    assert_eq!(fixed_index_vec.len(), 3);
+    assert_eq!(fixed_index_vec.get(0), Some(&'a'));
+    assert_eq!(fixed_index_vec.get(2), Some(&'c'));
+    assert!(!fixed_index_vec.contains_index(1));
+    assert_eq!(fixed_index_vec.empty_spaces_len(), 1);
+    assert_eq!(fixed_index_vec.lowest_free_index(), 1);
+    assert_consistent(&fixed_index_vec);
+}
+
+#[test]
+fn compress_observed_reports_the_same_moves_as_compress() {
+    use core::cell::RefCell;
+
+    use crate::fixed_index_vec::compact_observer::CompactObserver;
+
+    #[derive(Default)]
+    struct Recorder {
+        moves: RefCell<Vec<(usize, usize)>>,
+        completed: RefCell<Vec<usize>>,
+    }
+
+    impl CompactObserver for Recorder {
+        fn on_move(&self, old: usize, new: usize) {
+            self.moves.borrow_mut().push((old, new));
+        }
+
+        fn on_complete(&self, moves: usize) {
+            self.completed.borrow_mut().push(moves);
+        }
+    }
+
+    let mut fixed_index_vec: FixedIndexVec<u32> = (0..8).collect();
+    fixed_index_vec.remove(1);
+    fixed_index_vec.remove(3);
+    fixed_index_vec.remove(4);
+    fixed_index_vec.reserve_pos();
+    let mut reference = fixed_index_vec.clone();
+    let expected = reference.compress(true).0;
+    let recorder = Recorder::default();
+    fixed_index_vec.compress_observed(&recorder);
+    assert_eq!(*recorder.moves.borrow(), expected);
+    assert_eq!(*recorder.completed.borrow(), vec![expected.len()]);
+    assert_eq!(fixed_index_vec, reference);
+    assert_consistent(&fixed_index_vec);
+}
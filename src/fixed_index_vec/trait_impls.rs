@@ -1,4 +1,6 @@
-use alloc::vec::IntoIter;
+use alloc::collections::VecDeque;
+use alloc::vec::{IntoIter, Vec};
+use core::fmt::{Debug, Display, Formatter};
 use core::iter::Map;
 use core::panic::{RefUnwindSafe, UnwindSafe};
 use core::slice::{Iter, IterMut, SliceIndex};
@@ -6,15 +8,37 @@ use core::slice::{Iter, IterMut, SliceIndex};
 use crate::fixed_index_vec::FixedIndexVec;
 use crate::fixed_index_vec::pos::Pos;
 use crate::fixed_index_vec::pos::Pos::Used;
+use crate::fixed_index_vec::vec_index::VecIndex;
 
-impl<Value> Default for FixedIndexVec<Value> {
-    /// Creates an empty [FixedIndexVec], this is the same as calling [FixedIndexVec::new].
+impl<Value, Idx: VecIndex> Default for FixedIndexVec<Value, Idx> {
+    /// Creates an empty [FixedIndexVec], this is the same as calling [FixedIndexVec::new] or
+    /// [FixedIndexVec::with_index_type].
     fn default() -> Self {
-        Self::new()
+        Self::with_index_type()
     }
 }
 
-impl<'value, Value: Clone> Extend<&'value Value> for FixedIndexVec<Value> {
+impl<Value: Clone, Idx: Clone> Clone for FixedIndexVec<Value, Idx> {
+    /// Clones every position and vacancy, giving the clone the same capacities as the original.
+    fn clone(&self) -> Self {
+        let mut values = Vec::with_capacity(self.values.capacity());
+        values.extend(self.values.iter().cloned());
+        let mut vacancies = VecDeque::with_capacity(self.vacancies.capacity());
+        vacancies.extend(self.vacancies.iter().cloned());
+        Self {
+            values,
+            vacancies,
+            reserved_spaces: self.reserved_spaces,
+            reuse_policy: self.reuse_policy,
+            recently_freed: self.recently_freed.clone(),
+            auto_shrink: self.auto_shrink,
+            #[cfg(debug_assertions)]
+            generations: self.generations.clone(),
+        }
+    }
+}
+
+impl<'value, Value: Clone, Idx: VecIndex> Extend<&'value Value> for FixedIndexVec<Value, Idx> {
     /// Extends the values from the iterator by cloning them applying [FixedIndexVec::push] on every
     /// value.
     fn extend<T: IntoIterator<Item=&'value Value>>(&mut self, iter: T) {
@@ -22,14 +46,11 @@ impl<'value, Value: Clone> Extend<&'value Value> for FixedIndexVec<Value> {
     }
 }
 
-impl<Value> Extend<Value> for FixedIndexVec<Value> {
+impl<Value, Idx: VecIndex> Extend<Value> for FixedIndexVec<Value, Idx> {
     /// Extends the values from the iterator by applying [FixedIndexVec::push] on every value.
     fn extend<T: IntoIterator<Item=Value>>(&mut self, iter: T) {
         iter.into_iter().for_each(|value| {
-            match self.vacancies.pop_front() {
-                Some(vacant_index) => self.values[vacant_index] = Used(value),
-                None => self.values.push(Used(value)),
-            }
+            self.push(value);
         })
     }
 }
@@ -38,48 +59,77 @@ impl<Value, ValueIterator> From<ValueIterator> for FixedIndexVec<Value>
     where ValueIterator: IntoIterator<Item=Value> {
     /// Creates a new [FixedIndexVec] where every position is initially occupied by the items from
     /// the iterator.
+    ///
+    /// This is done through [FromIterator::from_iter].
     fn from(values: ValueIterator) -> Self {
-        Self {
-            values: values.into_iter().map(|value| Used(value)).collect(),
-            ..Self::new()
-        }
+        values.into_iter().collect()
     }
 }
 
-impl<Value> FromIterator<Value> for FixedIndexVec<Value> {
+impl<Value, Idx: VecIndex> FromIterator<Value> for FixedIndexVec<Value, Idx> {
     /// Creates a new [FixedIndexVec] where every position is initially occupied by the items from
     /// the iterator.
     ///
-    /// This is done through [From::from].
+    /// # Panics
+    /// Panics if the index type can't represent the index of every item.
     fn from_iter<T: IntoIterator<Item=Value>>(iter: T) -> Self {
-        Self::from(iter)
+        let values: Vec<Pos<Value>> = iter.into_iter().map(|value| Used(value)).collect();
+        if let Some(last_position) = values.len().checked_sub(1) {
+            assert!(Idx::from_usize(last_position).is_some(), "FixedIndexVec has no index left that its index type can represent");
+        }
+        Self {
+            values,
+            ..Self::with_index_type()
+        }
     }
 }
 
-impl<Value, Index> core::ops::Index<Index> for FixedIndexVec<Value> where Index: SliceIndex<[Pos<Value>]>, {
+impl<Value, Idx, Index> core::ops::Index<Index> for FixedIndexVec<Value, Idx>
+    where Index: SliceIndex<[Pos<Value>]> + Debug + Clone, {
     type Output = Index::Output;
 
     /// Obtains a reference to the position corresponding to this value.
     ///
     /// Note this is not the same as a value, as [Pos] also represent empty and reserved positions,
-    /// not just positions filled with values.
+    /// not just positions filled with values, meaning indexing into a position that holds no value
+    /// doesn't panic, but returns [Pos::Empty] or [Pos::Reserved], if you want just values, use
+    /// [FixedIndexVec::get] instead.
+    ///
+    /// # Panics
+    /// Panics if the index is out of range, telling both the index and the length of the Vec.
     fn index(&self, index: Index) -> &Self::Output {
-        core::ops::Index::index(&self.values, index)
+        match self.values.get(index.clone()) {
+            Some(output) => output,
+            None => panic!("FixedIndexVec index {index:?} out of range (len {})", self.values.len()),
+        }
     }
 }
 
-impl<Value, Index> core::ops::IndexMut<Index> for FixedIndexVec<Value> where Index: SliceIndex<[Pos<Value>]>, {
+impl<Value, Idx, Index> core::ops::IndexMut<Index> for FixedIndexVec<Value, Idx>
+    where Index: SliceIndex<[Pos<Value>]> + Debug + Clone, {
     /// Obtains a mutable reference to the position corresponding to this value.
     ///
     /// Note this is not the same as a value, as [Pos] also represent empty and reserved positions,
-    /// not just positions filled with values.
+    /// not just positions filled with values, meaning indexing into a position that holds no value
+    /// doesn't panic, but returns [Pos::Empty] or [Pos::Reserved], if you want just values, use
+    /// [FixedIndexVec::get_mut] instead.
+    ///
+    /// Changing the variant of a position through this reference isn't tracked, so
+    /// [FixedIndexVec::recompute_bookkeeping] must be called afterward.
+    ///
+    /// # Panics
+    /// Panics if the index is out of range, telling both the index and the length of the Vec.
     fn index_mut(&mut self, index: Index) -> &mut Self::Output {
-        core::ops::IndexMut::index_mut(&mut self.values, index)
+        let len = self.values.len();
+        match self.values.get_mut(index.clone()) {
+            Some(output) => output,
+            None => panic!("FixedIndexVec index {index:?} out of range (len {len})"),
+        }
     }
 }
 
 
-impl<'selflf, Value> IntoIterator for &'selflf FixedIndexVec<Value> {
+impl<'selflf, Value, Idx> IntoIterator for &'selflf FixedIndexVec<Value, Idx> {
     type Item = Option<&'selflf Value>;
     type IntoIter = Map<Iter<'selflf, Pos<Value>>, fn(&Pos<Value>) -> Option<&Value>>;
 
@@ -91,7 +141,7 @@ impl<'selflf, Value> IntoIterator for &'selflf FixedIndexVec<Value> {
     }
 }
 
-impl<'selflf, Value> IntoIterator for &'selflf mut FixedIndexVec<Value> {
+impl<'selflf, Value, Idx> IntoIterator for &'selflf mut FixedIndexVec<Value, Idx> {
     type Item = Option<&'selflf mut Value>;
     type IntoIter = Map<IterMut<'selflf, Pos<Value>>, fn(&mut Pos<Value>) -> Option<&mut Value>>;
 
@@ -103,7 +153,7 @@ impl<'selflf, Value> IntoIterator for &'selflf mut FixedIndexVec<Value> {
     }
 }
 
-impl<'selflf, Value> IntoIterator for FixedIndexVec<Value> {
+impl<Value, Idx> IntoIterator for FixedIndexVec<Value, Idx> {
     type Item = Option<Value>;
     type IntoIter = Map<IntoIter<Pos<Value>>, fn(Pos<Value>) -> Option<Value>>;
 
@@ -115,15 +165,17 @@ impl<'selflf, Value> IntoIterator for FixedIndexVec<Value> {
     }
 }
 
-impl<Value: PartialEq> PartialEq for FixedIndexVec<Value> {
+impl<Value: PartialEq, Idx: PartialEq> PartialEq for FixedIndexVec<Value, Idx> {
     /// Compares if two [FixedIndexVec] contents are equal, trying to compare them in the most
     /// efficient way.
+    #[allow(clippy::if_same_then_else)]
     fn eq(&self, other: &Self) -> bool {
         let lengths_are_equal = self.reserved_spaces.eq(&other.reserved_spaces)
             && self.vacancies.len().eq(&other.vacancies.len())
             && self.values.len().eq(&other.values.len());
         if !lengths_are_equal { return false; };
-        if self.used_spaces_len() > self.vacancies.len() {
+        let used_spaces = self.values.len() - (self.reserved_spaces + self.vacancies.len());
+        if used_spaces > self.vacancies.len() {
             self.values.eq(&other.values)
                 && self.vacancies.eq(&other.vacancies)
         } else {
@@ -131,21 +183,110 @@ impl<Value: PartialEq> PartialEq for FixedIndexVec<Value> {
                 && self.values.eq(&other.values)
         }
     }
+}
 
-    /// Compares if two [FixedIndexVec] contents are different, trying to compare them in the most
-    /// efficient way.
-    fn ne(&self, other: &Self) -> bool {
-        !self.eq(other)
+impl<Value: Display, Idx: VecIndex> Display for FixedIndexVec<Value, Idx> {
+    /// Writes every stored value along its index as `index: value`, separated by commas, without
+    /// allocating, for example `0: ValueA, 2: ValueC`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (position, (index, value)) in self.iter_index().enumerate() {
+            if position > 0 { f.write_str(", ")?; }
+            write!(f, "{}: {value}", index.into_usize())?;
+        }
+        Ok(())
     }
 }
 
 
-impl<Value: RefUnwindSafe> RefUnwindSafe for FixedIndexVec<Value> {}
+impl<Value: RefUnwindSafe, Idx: RefUnwindSafe> RefUnwindSafe for FixedIndexVec<Value, Idx> {}
+
+unsafe impl<Value: Send, Idx: Send> Send for FixedIndexVec<Value, Idx> {}
+
+unsafe impl<Value: Sync, Idx: Sync> Sync for FixedIndexVec<Value, Idx> {}
 
-unsafe impl<Value: Send> Send for FixedIndexVec<Value> {}
+impl<Value: Unpin, Idx: Unpin> Unpin for FixedIndexVec<Value, Idx> {}
 
-unsafe impl<Value: Sync> Sync for FixedIndexVec<Value> {}
+impl<Value: UnwindSafe, Idx: UnwindSafe> UnwindSafe for FixedIndexVec<Value, Idx> {}
 
-impl<Value: Unpin> Unpin for FixedIndexVec<Value> {}
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
 
-impl<Value: UnwindSafe> UnwindSafe for FixedIndexVec<Value> {}
+    use crate::fixed_index_vec::FixedIndexVec;
+    use crate::fixed_index_vec::pos::Pos;
+
+    /// Writes into a fixed stack buffer, failing once it's full, so formatting can't allocate.
+    struct StackBuffer {
+        bytes: [u8; 64],
+        len: usize,
+    }
+
+    impl StackBuffer {
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+        }
+    }
+
+    impl Write for StackBuffer {
+        fn write_str(&mut self, text: &str) -> core::fmt::Result {
+            let end = self.len + text.len();
+            if end > self.bytes.len() { return Err(core::fmt::Error); }
+            self.bytes[self.len..end].copy_from_slice(text.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn display_writes_used_values_with_their_indexes() {
+        let mut fixed_index_vec: FixedIndexVec<char> = ['a', 'b', 'c', 'd'].into_iter().collect();
+        fixed_index_vec.remove(1);
+        fixed_index_vec.reserve_pos();
+        fixed_index_vec.remove(3);
+        let mut buffer = StackBuffer { bytes: [0; 64], len: 0 };
+        write!(buffer, "{fixed_index_vec}").unwrap();
+        assert_eq!(buffer.as_str(), "0: a, 2: c");
+
+        let mut buffer = StackBuffer { bytes: [0; 64], len: 0 };
+        write!(buffer, "{}", FixedIndexVec::<char>::new()).unwrap();
+        assert_eq!(buffer.as_str(), "");
+    }
+
+    #[test]
+    fn indexing_returns_positions_even_without_values() {
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+        fixed_index_vec.remove(1);
+        assert_eq!(fixed_index_vec[0], Pos::Used(0));
+        assert_eq!(fixed_index_vec[1], Pos::Empty);
+        assert_eq!(fixed_index_vec[0..2].len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "FixedIndexVec index 5 out of range (len 3)")]
+    fn indexing_out_of_range_panics_telling_the_index_and_length() {
+        let fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+        let _ = &fixed_index_vec[5];
+    }
+
+    #[test]
+    #[should_panic(expected = "FixedIndexVec index 3 out of range (len 3)")]
+    fn mutable_indexing_out_of_range_panics_telling_the_index_and_length() {
+        let mut fixed_index_vec: FixedIndexVec<u32> = (0..3).collect();
+        fixed_index_vec[3] = Pos::Empty;
+    }
+
+    #[test]
+    fn clone_keeps_the_capacities_of_the_original() {
+        let mut fixed_index_vec = FixedIndexVec::with_capacity_and_vacancies(100, 40);
+        for value in 0..37 {
+            fixed_index_vec.push(value);
+        }
+        fixed_index_vec.remove(3);
+        fixed_index_vec.remove(9);
+        let clone = fixed_index_vec.clone();
+        assert_eq!(clone, fixed_index_vec);
+        assert_eq!(clone.capacity(), fixed_index_vec.capacity());
+        assert_eq!(clone.vacancies.capacity(), fixed_index_vec.vacancies.capacity());
+        assert_eq!(clone.reserved_spaces_len(), fixed_index_vec.reserved_spaces_len());
+    }
+}
@@ -0,0 +1,8 @@
+/// Tells [super::FixedIndexVec::update] what to do with a value once it was updated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpdateAction {
+    /// Leaves the value in place.
+    Keep,
+    /// Removes the value as [super::FixedIndexVec::remove] does.
+    Remove,
+}
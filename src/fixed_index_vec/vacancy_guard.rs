@@ -0,0 +1,70 @@
+use alloc::vec::Vec;
+use core::mem;
+
+use crate::fixed_index_vec::FixedIndexVec;
+use crate::fixed_index_vec::pos::Pos::Used;
+use crate::fixed_index_vec::vec_index::VecIndex;
+
+/// Holds the indexes of the positions of a [FixedIndexVec] emptied by an operation that removes
+/// many values at once, registering them as vacancies once dropped.
+/// <br>
+/// <br>
+/// As it's also dropped while unwinding, positions emptied before a predicate or a value's drop
+/// panics are never left empty without being registered.
+pub(super) struct VacancyGuard<'values, Value, Idx: VecIndex> {
+    pub(super) fixed_index_vec: &'values mut FixedIndexVec<Value, Idx>,
+    pub(super) freed_indexes: Vec<Idx>,
+}
+
+impl<'values, Value, Idx: VecIndex> VacancyGuard<'values, Value, Idx> {
+    pub(super) fn new(fixed_index_vec: &'values mut FixedIndexVec<Value, Idx>) -> Self {
+        VacancyGuard { fixed_index_vec, freed_indexes: Vec::new() }
+    }
+
+    /// Takes the value out of this position, which must hold one, leaving it empty to be
+    /// registered once this guard is dropped.
+    pub(super) fn take(&mut self, position: usize) -> Value {
+        let value = mem::take(&mut self.fixed_index_vec.values[position]).opt().unwrap();
+        self.fixed_index_vec.bump_generation(position);
+        self.freed_indexes.push(FixedIndexVec::<Value, Idx>::index_of(position));
+        value
+    }
+
+    /// Registers the positions emptied so far as vacancies and performs
+    /// [FixedIndexVec::clean_right].
+    pub(super) fn register(&mut self) {
+        let mut freed_indexes = mem::take(&mut self.freed_indexes);
+        freed_indexes.sort_unstable();
+        self.fixed_index_vec.merge_vacancies(freed_indexes);
+        self.fixed_index_vec.clean_right();
+    }
+}
+
+impl<Value, Idx: VecIndex> Drop for VacancyGuard<'_, Value, Idx> {
+    fn drop(&mut self) {
+        self.register();
+    }
+}
+
+/// Refers to a position of a [FixedIndexVec] whose value was taken out to compute a new one,
+/// registering it as a vacancy if dropped before a value is put back, as happens when computing
+/// the new value panics.
+pub(super) struct TakenValueGuard<'values, Value, Idx: VecIndex> {
+    pub(super) fixed_index_vec: &'values mut FixedIndexVec<Value, Idx>,
+    pub(super) index: Idx,
+}
+
+impl<Value, Idx: VecIndex> TakenValueGuard<'_, Value, Idx> {
+    /// Stores the value on the position, leaving it as used rather than registering it as a
+    /// vacancy.
+    pub(super) fn put_back(self, value: Value) {
+        self.fixed_index_vec.values[self.index.into_usize()] = Used(value);
+        mem::forget(self);
+    }
+}
+
+impl<Value, Idx: VecIndex> Drop for TakenValueGuard<'_, Value, Idx> {
+    fn drop(&mut self) {
+        self.fixed_index_vec.register_vacancy(self.index);
+    }
+}
@@ -0,0 +1,35 @@
+use core::fmt::Debug;
+
+/// Types that can be used as indexes of a [super::FixedIndexVec], allowing to use types smaller
+/// than usize, such as u32, to reduce the memory used to keep track of empty positions.
+/// <br>
+/// <br>
+/// It's implemented for every unsigned integer, but it can also be implemented for other types,
+/// such as wrappers over them.
+pub trait VecIndex: Copy + Ord + Debug {
+    /// Converts the position into an index, returning [Option::None] if this type can't
+    /// represent it.
+    fn from_usize(position: usize) -> Option<Self>;
+
+    /// Converts the index into a position, returning [usize::MAX] if it can't be represented as a
+    /// usize, as that position can never exist.
+    fn into_usize(self) -> usize;
+}
+
+macro_rules! impl_vec_index {
+    ($($index_type:ty),*) => {
+        $(
+            impl VecIndex for $index_type {
+                fn from_usize(position: usize) -> Option<Self> {
+                    <$index_type>::try_from(position).ok()
+                }
+
+                fn into_usize(self) -> usize {
+                    usize::try_from(self).unwrap_or(usize::MAX)
+                }
+            }
+        )*
+    };
+}
+
+impl_vec_index!(u8, u16, u32, u64, usize);
@@ -1,8 +1,9 @@
 //! Vec-like structure where indexes are kept for values even when removing others, usually used to
 //! replace HashMap<usize, T> on environments where std can't reach or when performance of accessing
 //! by index is extremely important but can do get it at the expense of memory allocation,
-//! especially when the removal operation is not required or used often as every operation is O(1),
-//! where the access is done through a Vec, not requiring hashing operations.
+//! especially when the removal operation is not required or used often as pushing and accessing are
+//! O(1) under the default [fixed_index_vec::reuse_policy::ReusePolicy], where the access is done
+//! through a Vec, not requiring hashing operations.
 //! <br>
 //! <br>
 //! This is implemented using a Vec where the values are stored, once a value is inserted, an index
@@ -63,7 +64,6 @@
 //! receiving an iterator of previous indexes and replaces them for the new ones.
 
 #![no_std]
-#![no_main]
 
 extern crate alloc;
 